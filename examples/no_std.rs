@@ -0,0 +1,23 @@
+//! Using the iterator buffer without the standard library.
+//!
+//! Build with `cargo build --no-default-features --example no_std`.
+
+#![no_std]
+
+extern crate iterator_utilities;
+
+use iterator_utilities::buffer::IteratorBuffer;
+
+/// Count the elements of `xs` that are immediately followed by a
+/// larger element.
+pub fn count_rising(xs: &[u32]) -> usize {
+    let mut ib = IteratorBuffer::new(xs.iter(), 1);
+    let mut count = 0;
+    while ib.len() > 1 {
+        if ib[0] < ib[1] {
+            count += 1;
+        }
+        ib.pop();
+    }
+    count
+}
@@ -1,6 +1,15 @@
 //! Iterator buffer: temporarily store and allow access to several elements of an iterator.
 
-use std::ops::{Index,IndexMut};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::{Index,IndexMut};
+use core::slice;
+#[cfg(test)]
+use alloc::string::ToString;
+
+#[cfg(feature = "serde")]
+use serde::{Serialize,Deserialize};
 
 /// A variable-size buffer reading from an iterator and providing
 /// access to future elements of the stream.
@@ -12,6 +21,30 @@ pub struct IteratorBuffer<I:Iterator> {
     buffer:   Vec<I::Item>,
 }
 
+/// A copy of the buffered contents and state of an IteratorBuffer.
+/// The contained iterator is not captured.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BufferSnapshot<T> {
+    pub buffer:  Vec<T>,
+    pub opening: bool,
+    pub closing: bool,
+}
+
+/// The error returned by `try_pop` when the stream is exhausted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BufferEmpty;
+
+impl fmt::Display for BufferEmpty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("iterator buffer is empty")
+    }
+}
+
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
+impl ::std::error::Error for BufferEmpty { }
+
 impl<I> IteratorBuffer<I> where I: Iterator, I::Item: Clone {
 
     /// Create a buffer for Iterator it, of size elements.
@@ -35,16 +68,26 @@ impl<I> IteratorBuffer<I> where I: Iterator, I::Item: Clone {
     /// the contained iterator.
     pub fn is_opening(&self) -> bool { self.opening }
 
-    /// Return false if the contained iterator has yielded None; the
-    /// only remaining elements are in the buffer.
+    /// Return true if the contained iterator has yielded None; the
+    /// only remaining elements are in the buffer. The stream is only
+    /// exhausted once the buffer is also empty.
     pub fn is_closing(&self) -> bool { self.closing }
 
+    /// Return true if the contained iterator may yield more
+    /// elements, beyond those in the buffer. An empty buffer with
+    /// more to come has not reached the end of the stream.
+    pub fn has_more(&self) -> bool { !self.closing }
+
     /// The current length of the buffer. Pending elements from the
     /// iterator, if any, are not counted.
     pub fn len(&self) -> usize { self.buffer.len() }
 
+    /// Return true if the buffer is currently empty. Pending elements
+    /// from the iterator, if any, are not counted.
+    pub fn is_empty(&self) -> bool { self.buffer.is_empty() }
+
     /// Provide read-only access to the buffer itself.
-    pub fn buffer<'a>(&'a self) -> &'a [I::Item] {
+    pub fn buffer(&self) -> &[I::Item] {
         &self.buffer
     }
 
@@ -62,20 +105,284 @@ impl<I> IteratorBuffer<I> where I: Iterator, I::Item: Clone {
         }
     }
 
+    /// Yield the next element from the buffer, as `pop`, treating
+    /// the end of the stream as an error.
+    pub fn try_pop(&mut self) -> Result<I::Item,BufferEmpty> {
+        self.pop().ok_or(BufferEmpty)
+    }
+
     /// Replace `len` elements from the buffer with copies of the
     /// contents of `replacement`.
     pub fn replace(&mut self, len: usize, replacement: &[I::Item]) {
         for _ in 0..len {
             self.buffer.remove(0);
         }
-        for i in 0..replacement.len() {
-            self.buffer.insert(i, replacement[i].clone());
+        for (i, item) in replacement.iter().enumerate() {
+            self.buffer.insert(i, item.clone());
+        }
+    }
+
+    /// Return true if at least `len` elements remain in the stream
+    /// and `pred` accepts the first `len` of them. The buffer is
+    /// filled as needed, so `len` may exceed the buffer's size.
+    pub fn peek_match<P>(&mut self, len: usize, mut pred: P) -> bool
+        where P: FnMut(&[I::Item]) -> bool {
+        self.fill_to(len);
+        if len <= self.buffer.len() {
+            pred(&self.buffer[..len])
+        } else {
+            false
+        }
+    }
+
+    /// Copy the current buffered contents and the opening and
+    /// closing flags. With the `serde` feature, the snapshot can be
+    /// serialized for debugging or checkpointing.
+    pub fn snapshot(&self) -> BufferSnapshot<I::Item> {
+        BufferSnapshot {
+            buffer:  self.buffer.clone(),
+            opening: self.opening,
+            closing: self.closing,
+        }
+    }
+
+    /// Return the last element currently in the buffer, if any. This
+    /// does not read from the iterator, so it is the last buffered
+    /// element, not the last element of the stream.
+    pub fn peek_back(&self) -> Option<&I::Item> {
+        self.buffer.last()
+    }
+
+    /// Insert `item` at the front of the buffer, so that it is the
+    /// next element yielded. No element is read from the iterator.
+    pub fn push_front(&mut self, item: I::Item) {
+        self.buffer.insert(0, item);
+    }
+
+    /// Insert copies of `items` at the front of the buffer, in order,
+    /// so that they are the next elements yielded.
+    pub fn extend_front(&mut self, items: &[I::Item]) {
+        self.buffer.splice(0..0, items.iter().cloned());
+    }
+
+    /// Discard leading elements while `pred` holds, returning the
+    /// number discarded. The first element not satisfying `pred`, if
+    /// any, is left at the front of the buffer.
+    pub fn trim_start<P>(&mut self, mut pred: P) -> usize
+        where P: FnMut(&I::Item) -> bool {
+        let mut count = 0;
+        while self.peek().is_some_and(&mut pred) {
+            self.pop();
+            count += 1;
+        }
+        count
+    }
+
+    /// Remove and return leading elements while `pred` holds. The
+    /// first element not satisfying `pred`, if any, is left at the
+    /// front of the buffer.
+    pub fn read_while<P>(&mut self, mut pred: P) -> Vec<I::Item>
+        where P: FnMut(&I::Item) -> bool {
+        let mut res = Vec::new();
+        while self.peek().is_some_and(&mut pred) {
+            res.extend(self.pop());
+        }
+        res
+    }
+
+    /// Return a copy of the current contents of the buffer. Nothing
+    /// is read from the iterator.
+    pub fn as_vec(&self) -> Vec<I::Item> {
+        self.buffer.clone()
+    }
+
+    /// Remove and return leading elements up to the first boundary,
+    /// where `pred(prev, next)` returns true for adjacent elements
+    /// `prev` and `next`. The element after the boundary is left at
+    /// the front of the buffer.
+    pub fn split_when<P>(&mut self, mut pred: P) -> Vec<I::Item>
+        where P: FnMut(&I::Item, &I::Item) -> bool {
+        let mut res: Vec<I::Item> = self.pop().into_iter().collect();
+        while let (Some(prev), Some(next)) = (res.last(), self.buffer.first()) {
+            if pred(prev, next) {
+                break;
+            }
+            res.extend(self.pop());
+        }
+        res
+    }
+
+    /// Count the leading elements of the buffer satisfying `pred`,
+    /// without removing them. Only the current buffer is examined,
+    /// so the count is at most `len()`.
+    pub fn count_while<P>(&self, mut pred: P) -> usize
+        where P: FnMut(&I::Item) -> bool {
+        self.buffer.iter().take_while(|item| pred(item)).count()
+    }
+
+    /// Bounds on the number of elements remaining in the stream,
+    /// including those in the buffer, as for `Iterator::size_hint`.
+    pub fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.buffer.len();
+        if self.closing {
+            (len, Some(len))
+        } else {
+            let (lo, hi) = self.iterator.size_hint();
+            (lo.saturating_add(len), hi.and_then(|hi| hi.checked_add(len)))
+        }
+    }
+
+    /// Return the next element of the stream without removing it, as
+    /// `Peekable::peek`. With `pop`, or the `Iterator`
+    /// implementation, the buffer can be used in place of a
+    /// `Peekable`.
+    pub fn peek(&mut self) -> Option<&I::Item> {
+        self.fill();
+        self.buffer.first()
+    }
+
+    /// Remove and return the next `size` elements of the stream, or
+    /// fewer if the stream ends first. Returns None once the stream
+    /// is exhausted. Panics if `size` is 0.
+    pub fn take_chunk(&mut self, size: usize) -> Option<Vec<I::Item>> {
+        assert!(size != 0, "chunk size must be non-zero");
+        let chunk: Vec<I::Item> = self.by_ref().take(size).collect();
+        if chunk.is_empty() { None } else { Some(chunk) }
+    }
+
+    /// Remove the buffered elements not satisfying `pred`. Only the
+    /// current buffer is affected; elements still pending in the
+    /// iterator are not examined, and the buffer is not refilled
+    /// until the next `pop`.
+    pub fn retain_buffer<P>(&mut self, mut pred: P)
+        where P: FnMut(&I::Item) -> bool {
+        self.buffer.retain(|item| pred(item));
+    }
+
+    /// Apply `f` to each buffered element in place. Elements still
+    /// pending in the iterator are not affected, even once they are
+    /// read into the buffer.
+    pub fn map_buffer<F>(&mut self, f: F)
+        where F: FnMut(&mut I::Item) {
+        self.buffer.iter_mut().for_each(f);
+    }
+
+    /// Return the index in the buffer of the first element
+    /// satisfying `pred`. Only the current buffer is examined, so an
+    /// element beyond `len()` is not found.
+    pub fn position_buffer<P>(&self, pred: P) -> Option<usize>
+        where P: FnMut(&I::Item) -> bool {
+        self.buffer.iter().position(pred)
+    }
+
+    /// Remove and return the elements before the first element
+    /// satisfying `pred`, which is left at the front of the
+    /// buffer. If no element satisfies `pred`, the whole remaining
+    /// stream is returned.
+    pub fn drain_until<P>(&mut self, mut pred: P) -> Vec<I::Item>
+        where P: FnMut(&I::Item) -> bool {
+        self.read_while(|item| !pred(item))
+    }
+
+    /// Iterate over references to the buffered elements, without
+    /// removing them.
+    pub fn iter_buffer(&self) -> slice::Iter<'_,I::Item> {
+        self.buffer.iter()
+    }
+
+    /// Read elements from the iterator into the buffer while
+    /// `pred(buffer)` holds. The buffer grows as needed; only the end
+    /// of the stream limits it.
+    pub fn fill_while<P>(&mut self, mut pred: P)
+        where P: FnMut(&[I::Item]) -> bool {
+        while !self.closing && pred(&self.buffer) {
+            let len = self.buffer.len() + 1;
+            self.fill_to(len);
+        }
+    }
+
+    /// Swap two elements of the buffer. Panics if either index is
+    /// out of bounds.
+    pub fn swap(&mut self, i: usize, j: usize) {
+        self.buffer.swap(i, j);
+    }
+
+    /// Fold `f` over the buffered elements, without removing them.
+    pub fn fold_buffer<B,F>(&self, init: B, f: F) -> B
+        where F: FnMut(B, &I::Item) -> B {
+        self.buffer.iter().fold(init, f)
+    }
+
+    /// Return the remainder of the stream, the buffered elements
+    /// followed by those left in the iterator, consuming the buffer.
+    pub fn collect_remaining(self) -> Vec<I::Item> {
+        let mut rest = self.buffer;
+        if !self.closing {
+            rest.extend(self.iterator);
+        }
+        rest
+    }
+
+    /// Return the element at `index` in the buffer, or None if the
+    /// buffer is shorter, rather than panicking as indexing does.
+    pub fn get(&self, index: usize) -> Option<&I::Item> {
+        self.buffer.get(index)
+    }
+
+    /// Return the element at `index` in the buffer, as `get`, first
+    /// reading from the iterator as needed to reach it. The buffer
+    /// grows beyond its size if necessary; only the end of the
+    /// stream limits it.
+    pub fn get_or_fill(&mut self, index: usize) -> Option<&I::Item> {
+        self.fill_to(index.saturating_add(1));
+        self.buffer.get(index)
+    }
+
+    /// Rotate the buffered elements so that the element at `mid`
+    /// becomes the first, as `slice::rotate_left`. Nothing is read
+    /// from the iterator. Panics if `mid` is greater than `len()`.
+    pub fn rotate_buffer(&mut self, mid: usize) {
+        self.buffer.rotate_left(mid);
+    }
+
+    /// Discard leading elements until one satisfies `pred`,
+    /// returning the number discarded. The element satisfying
+    /// `pred` is left at the front of the buffer; if there is none,
+    /// the whole remaining stream is discarded.
+    pub fn align_to<P>(&mut self, mut pred: P) -> usize
+        where P: FnMut(&I::Item) -> bool {
+        self.trim_start(|item| !pred(item))
+    }
+
+    /// Return the length of the leading run of elements satisfying
+    /// `pred`. The buffer grows as needed to find the end of the
+    /// run, so the count is not limited by the buffer's size, only
+    /// by the end of the stream.
+    pub fn lookahead_match_len<P>(&mut self, mut pred: P) -> usize
+        where P: FnMut(&I::Item) -> bool {
+        self.fill();
+        let mut count = 0;
+        loop {
+            while count < self.buffer.len() && pred(&self.buffer[count]) {
+                count += 1;
+            }
+            if count < self.buffer.len() || self.closing {
+                return count;
+            }
+            self.fill_to(count + 1);
         }
     }
 
     /// Fill the buffer from the iterator, setting closing if needed.
     fn fill(&mut self) {
-        while !self.closing && self.buffer.len() < self.size {
+        let size = self.size;
+        self.fill_to(size);
+    }
+
+    /// Fill the buffer from the iterator until it holds at least
+    /// `len` elements, setting closing if needed.
+    fn fill_to(&mut self, len: usize) {
+        while !self.closing && self.buffer.len() < len {
             match self.iterator.next() {
                 Some(item) => { self.buffer.push(item) }
                 None       => { self.closing = true; }
@@ -105,19 +412,96 @@ impl<I> IteratorBuffer<I> where I: Iterator, I::Item: Clone + PartialEq {
     /// this to be meaningful, the buffer must be larger than the
     /// suffix.
     pub fn ends_with(&self, suffix: &[I::Item]) -> bool {
-        if self.closing && suffix.len() == self.buffer.len() {
+        if self.closing && suffix.len() <= self.buffer.len() {
             self.buffer.ends_with(suffix)
         } else {
             false
         }
     }
+
+    /// Return the index of the first of `patterns` that the buffer
+    /// currently starts with. The buffer is first filled to the
+    /// length of the longest pattern, so the result does not depend
+    /// on the buffer's size. Unlike `starts_with`, this tests the
+    /// remaining stream, not its beginning.
+    pub fn matches_any(&mut self, patterns: &[&[I::Item]]) -> Option<usize> {
+        let longest = patterns.iter().map(|p| p.len()).max().unwrap_or(0);
+        self.fill_to(longest);
+        patterns.iter().position(|p| self.buffer.starts_with(p))
+    }
+
+    /// Return the index of the longest of `patterns` that the buffer
+    /// currently starts with, preferring the earliest of equally
+    /// long patterns. As with `matches_any`, the buffer is first
+    /// filled to the length of the longest pattern.
+    pub fn longest_match(&mut self, patterns: &[&[I::Item]]) -> Option<usize> {
+        let longest = patterns.iter().map(|p| p.len()).max().unwrap_or(0);
+        self.fill_to(longest);
+        let mut best: Option<usize> = None;
+        for (i, p) in patterns.iter().enumerate() {
+            if self.buffer.starts_with(p) && best.is_none_or(|b| patterns[b].len() < p.len()) {
+                best = Some(i);
+            }
+        }
+        best
+    }
+
+    /// Return true if the buffer currently starts with any of
+    /// `prefixes`, filling it to the length of the longest prefix
+    /// first, as `matches_any`.
+    pub fn starts_with_any(&mut self, prefixes: &[&[I::Item]]) -> bool {
+        self.matches_any(prefixes).is_some()
+    }
+
+    /// Return true if the iterator stream ends with any of
+    /// `suffixes`, as `ends_with`. This is false until the iterator
+    /// has returned None.
+    pub fn ends_with_any(&self, suffixes: &[&[I::Item]]) -> bool {
+        suffixes.iter().any(|suffix| self.ends_with(suffix))
+    }
+}
+
+/// Functions ordering the contents of the buffer.
+impl<I> IteratorBuffer<I> where I: Iterator, I::Item: Clone + Ord {
+
+    /// Compare the front of the stream, up to the length of `other`,
+    /// with `other`, lexicographically. The buffer is filled to the
+    /// length of `other` first; a stream ending before that compares
+    /// as less if it is a prefix of `other`.
+    pub fn compare_prefix(&mut self, other: &[I::Item]) -> Ordering {
+        self.fill_to(other.len());
+        let len = self.buffer.len().min(other.len());
+        self.buffer[..len].cmp(other)
+    }
+}
+
+impl<I> Iterator for IteratorBuffer<I> where I: Iterator, I::Item: Clone {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> { self.pop() }
+
+    fn size_hint(&self) -> (usize, Option<usize>) { IteratorBuffer::size_hint(self) }
+}
+
+impl<I> fmt::Debug for IteratorBuffer<I> where I: Iterator, I::Item: fmt::Debug {
+
+    /// Show the buffered elements and state; the contained iterator
+    /// is not shown.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("IteratorBuffer")
+            .field("buffer", &self.buffer)
+            .field("opening", &self.opening)
+            .field("closing", &self.closing)
+            .field("size", &self.size)
+            .finish()
+    }
 }
 
 impl<I> Index<usize> for IteratorBuffer<I> where I: Iterator, I::Item: Clone {
     type Output = I::Item;
 
     /// Provide access to an element in the buffer.
-    fn index<'a>(&'a self, index: usize) -> &'a I::Item {
+    fn index(&self, index: usize) -> &I::Item {
         &self.buffer[index]
     }
 }
@@ -125,12 +509,51 @@ impl<I> Index<usize> for IteratorBuffer<I> where I: Iterator, I::Item: Clone {
 impl<I> IndexMut<usize> for IteratorBuffer<I> where I: Iterator, I::Item: Clone {
 
     /// Provide mutable access to an element in the buffer.
-    fn index_mut<'a>(&'a mut self, index: usize) -> &'a mut I::Item {
+    fn index_mut(&mut self, index: usize) -> &mut I::Item {
         self.fill();
         &mut self.buffer[index]
     }
 }
 
+/// Construct an IteratorBuffer from any iterator, as a method.
+///
+/// ```
+/// use iterator_utilities::buffer::BufferExt;
+///
+/// let mut ib = (0..10).buffered(3);
+/// assert_eq!(ib.buffer(), &[0,1,2,3]);
+/// assert_eq!(ib.pop(), Some(0));
+/// ```
+pub trait BufferExt: Iterator + Sized {
+
+    /// As `IteratorBuffer::new(self, size)`.
+    fn buffered(self, size: usize) -> IteratorBuffer<Self> where Self::Item: Clone {
+        IteratorBuffer::new(self, size)
+    }
+}
+
+impl<I> BufferExt for I where I: Iterator { }
+
+/// If the front of the stream of `buf` is `prefix`, remove the
+/// prefix and return its length along with the advanced
+/// buffer. Otherwise, return the buffer, with nothing removed, as the
+/// error. The buffer is filled to the length of the prefix first.
+/// Unlike `starts_with`, the buffer need not be at the start of the
+/// stream, so successive frames can be split off in turn.
+pub fn prefix_and_rest<I>(mut buf: IteratorBuffer<I>, prefix: &[I::Item]) -> Result<(usize,IteratorBuffer<I>),IteratorBuffer<I>>
+    where I: Iterator, I::Item: Clone + PartialEq {
+    buf.fill_to(prefix.len());
+    if buf.buffer.starts_with(prefix) {
+        for _ in 0..prefix.len() {
+            buf.pop();
+        }
+        Ok((prefix.len(), buf))
+    } else {
+        Err(buf)
+    }
+}
+
+#[allow(unused_parens)]
 #[test]
 fn test1() {
     let mut ib = IteratorBuffer::new((0..4),2);
@@ -146,3 +569,418 @@ fn test1() {
     assert_eq!(ib.pop(), Some(3));
     assert_eq!(ib.pop(), None);
 }
+
+#[test]
+fn test2() {
+    let mut ib = IteratorBuffer::new("12a456".chars(), 2);
+    assert!(ib.peek_match(2, |s| s.iter().all(|c| c.is_ascii_digit())));
+    assert!(!ib.peek_match(3, |s| s.iter().all(|c| c.is_ascii_digit())));
+    assert!(ib.peek_match(6, |s| s.len() == 6));
+    assert!(!ib.peek_match(7, |_| true));
+    assert_eq!(ib.pop(), Some('1'));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test3() {
+    let mut ib = IteratorBuffer::new(0..4, 2);
+    ib.pop();
+    let snapshot = ib.snapshot();
+    assert_eq!(snapshot, BufferSnapshot { buffer: vec!{1,2,3}, opening: false, closing: false });
+    let json = ::serde_json::to_string(&snapshot).unwrap();
+    let restored: BufferSnapshot<i32> = ::serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, snapshot);
+}
+
+#[test]
+fn test4() {
+    let mut ib = IteratorBuffer::new(0..4, 2);
+    assert!(format!("{:?}", ib).contains("buffer: [0, 1, 2]"));
+    ib.pop();
+    ib.pop();
+    let debug = format!("{:?}", ib);
+    assert!(debug.contains("buffer: [2, 3]"));
+    assert!(debug.contains("opening: false"));
+    assert!(debug.contains("closing: true"));
+}
+
+#[test]
+fn test5() {
+    let mut ib = IteratorBuffer::new(0..5, 2);
+    assert_eq!(ib.peek_back(), Some(&2));
+    ib.pop();
+    ib.pop();
+    assert_eq!(ib.peek_back(), Some(&4));
+    ib.pop();
+    ib.pop();
+    ib.pop();
+    assert_eq!(ib.peek_back(), None);
+}
+
+#[test]
+fn test6() {
+    let mut ib = IteratorBuffer::new(0..3, 1);
+    ib.push_front(9);
+    assert!(ib.starts_with(&[9,0]));
+    assert_eq!(ib[0], 9);
+    assert_eq!(ib.pop(), Some(9));
+    ib.push_front(8);
+    assert_eq!(ib.pop(), Some(8));
+    assert_eq!(ib.pop(), Some(0));
+}
+
+#[test]
+fn test7() {
+    let mut ib = IteratorBuffer::new(0..2, 1);
+    ib.extend_front(&[7,8]);
+    assert_eq!(ib.buffer(), &[7,8,0,1]);
+    assert_eq!(ib.pop(), Some(7));
+    assert_eq!(ib.pop(), Some(8));
+    assert_eq!(ib.pop(), Some(0));
+    assert_eq!(ib.pop(), Some(1));
+    assert_eq!(ib.pop(), None);
+}
+
+#[test]
+fn test8() {
+    let mut ib = IteratorBuffer::new("<<=a".chars(), 1);
+    let patterns: &[&[char]] = &[&['<','<','='], &['<','<'], &['<']];
+    assert_eq!(ib.matches_any(patterns), Some(0));
+    assert_eq!(ib.matches_any(&[&['<'], &['<','<','=']]), Some(0));
+    ib.pop();
+    assert_eq!(ib.matches_any(patterns), Some(2));
+    ib.pop();
+    assert_eq!(ib.matches_any(patterns), None);
+}
+
+#[test]
+fn test9() {
+    let mut ib = IteratorBuffer::new("==a=b".chars(), 0);
+    let patterns: &[&[char]] = &[&['='], &['=','=']];
+    assert_eq!(ib.longest_match(patterns), Some(1));
+    assert_eq!(ib.matches_any(patterns), Some(0));
+    ib.pop();
+    ib.pop();
+    assert_eq!(ib.longest_match(patterns), None);
+    ib.pop();
+    assert_eq!(ib.longest_match(patterns), Some(0));
+    assert_eq!(ib.longest_match(&[&['=','b'], &['=','c'], &['=','b']]), Some(0));
+}
+
+#[test]
+fn test10() {
+    let mut ib = IteratorBuffer::new(vec!{0,0,1,2}.into_iter(), 1);
+    assert_eq!(ib.trim_start(|&n| n == 0), 2);
+    assert_eq!(ib.trim_start(|&n| n == 0), 0);
+    assert_eq!(ib.pop(), Some(1));
+    let mut ib = IteratorBuffer::new(vec!{0,0,0}.into_iter(), 0);
+    assert_eq!(ib.trim_start(|&n| n == 0), 3);
+    assert_eq!(ib.pop(), None);
+}
+
+#[test]
+fn test11() {
+    let mut ib = IteratorBuffer::new("123+45".chars(), 1);
+    assert_eq!(ib.read_while(|c| c.is_ascii_digit()), vec!{'1','2','3'});
+    assert_eq!(ib.read_while(|c| c.is_ascii_digit()), vec!{});
+    assert_eq!(ib.pop(), Some('+'));
+    assert_eq!(ib.read_while(|c| c.is_ascii_digit()), vec!{'4','5'});
+    assert_eq!(ib.pop(), None);
+}
+
+#[test]
+fn test12() {
+    let mut ib = IteratorBuffer::new(0..5, 2);
+    ib.pop();
+    let copy = ib.as_vec();
+    assert_eq!(copy, ib.buffer());
+    assert_eq!(copy, vec!{1,2,3});
+    assert_eq!(ib.pop(), Some(1));
+}
+
+#[test]
+fn test13() {
+    let mut ib = IteratorBuffer::new(vec!{1,2,3,1,2}.into_iter(), 1);
+    assert_eq!(ib.split_when(|prev, next| next < prev), vec!{1,2,3});
+    assert_eq!(ib[0], 1);
+    assert_eq!(ib.split_when(|prev, next| next < prev), vec!{1,2});
+    assert!(ib.split_when(|prev, next| next < prev).is_empty());
+}
+
+#[test]
+fn test14() {
+    let ib = IteratorBuffer::new("12+345".chars(), 5);
+    assert_eq!(ib.count_while(|c| c.is_ascii_digit()), 2);
+    assert_eq!(ib.count_while(|c| c.is_alphabetic()), 0);
+    let ib = IteratorBuffer::new("123456".chars(), 3);
+    assert_eq!(ib.count_while(|c| c.is_ascii_digit()), 4);
+}
+
+#[test]
+fn test15() {
+    let mut ib = IteratorBuffer::new(0..5, 1);
+    assert_eq!(ib.size_hint(), (5, Some(5)));
+    ib.pop();
+    ib.pop();
+    ib.pop();
+    assert_eq!(ib.size_hint(), (2, Some(2)));
+    let ib = IteratorBuffer::new((0..5).filter(|_| true), 1);
+    assert_eq!(ib.size_hint(), (2, Some(5)));
+}
+
+#[test]
+fn test16() {
+    let mut peekable = (0..3).peekable();
+    let mut ib = IteratorBuffer::new(0..3, 0);
+    assert_eq!(ib.peek(), peekable.peek());
+    assert_eq!(ib.peek(), Some(&0));
+    assert_eq!(ib.next(), peekable.next());
+    assert_eq!(ib.peek(), Some(&1));
+    assert_eq!(ib.collect::<Vec<_>>(), peekable.collect::<Vec<_>>());
+}
+
+#[test]
+fn test17() {
+    let mut ib = IteratorBuffer::new(0..7, 1);
+    let mut chunks = vec!{};
+    while let Some(chunk) = ib.take_chunk(3) {
+        chunks.push(chunk);
+    }
+    assert_eq!(chunks, vec!{vec!{0,1,2}, vec!{3,4,5}, vec!{6}});
+}
+
+#[test]
+fn test18() {
+    let mut ib = IteratorBuffer::new(0..10, 4);
+    ib.retain_buffer(|n| n % 2 == 0);
+    assert_eq!(ib.buffer(), &[0,2,4]);
+    assert_eq!(ib.pop(), Some(0));
+    assert_eq!(ib.buffer(), &[2,4,5,6,7]);
+}
+
+#[test]
+fn test19() {
+    let ib = IteratorBuffer::new("GET /".chars(), 1);
+    let ib = match prefix_and_rest(ib, &['P','U','T']) {
+        Ok(_)   => panic!("matched the wrong prefix"),
+        Err(ib) => ib,
+    };
+    assert!(ib.is_opening());
+    assert_eq!(ib[0], 'G');
+    let (len, mut ib) = prefix_and_rest(ib, &['G','E','T']).ok().unwrap();
+    assert_eq!(len, 3);
+    assert_eq!(ib.pop(), Some(' '));
+}
+
+#[test]
+fn test20() {
+    let mut ib = IteratorBuffer::new(1..6, 2);
+    ib.map_buffer(|n| *n *= 2);
+    assert_eq!(ib.buffer(), &[2,4,6]);
+    assert_eq!(ib.pop(), Some(2));
+    assert_eq!(ib.buffer(), &[4,6,4]);
+}
+
+#[test]
+fn test21() {
+    let ib = IteratorBuffer::new(vec!{3,1,-4,1,-5}.into_iter(), 3);
+    assert_eq!(ib.position_buffer(|&n| n < 0), Some(2));
+    assert_eq!(ib.position_buffer(|&n| n > 5), None);
+    let ib = IteratorBuffer::new(vec!{3,1,4,1,-5}.into_iter(), 3);
+    assert_eq!(ib.position_buffer(|&n| n < 0), None);
+}
+
+#[test]
+fn test22() {
+    let mut ib = IteratorBuffer::new("//x".chars(), 0);
+    assert!(ib.starts_with_any(&[&['/','*'], &['/','/']]));
+    assert!(!ib.starts_with_any(&[&['/','*'], &['#']]));
+    assert!(!ib.starts_with_any(&[]));
+    ib.pop();
+    ib.pop();
+    assert!(!ib.starts_with_any(&[&['x','y']]));
+}
+
+#[test]
+fn test23() {
+    let mut ib = IteratorBuffer::new(vec!{1,2,0,3}.into_iter(), 1);
+    assert_eq!(ib.drain_until(|&n| n == 0), vec!{1,2});
+    assert_eq!(ib.pop(), Some(0));
+    assert_eq!(ib.drain_until(|&n| n == 0), vec!{3});
+    assert_eq!(ib.pop(), None);
+}
+
+#[test]
+fn test24() {
+    let mut ib = IteratorBuffer::new("abc;\n".chars(), 2);
+    assert!(!ib.ends_with_any(&[&[';','\n'], &['\n']]));
+    ib.pop();
+    ib.pop();
+    ib.pop();
+    assert!(ib.is_closing());
+    assert!(ib.ends_with(&['\n']));
+    assert!(ib.ends_with_any(&[&['\r','\n'], &[';','\n']]));
+    assert!(ib.ends_with_any(&[&['x'], &['\n']]));
+    assert!(!ib.ends_with_any(&[&['\r','\n'], &['x']]));
+}
+
+#[test]
+fn test25() {
+    let mut ib = IteratorBuffer::new(0..5, 2);
+    ib.pop();
+    let refs: Vec<&i32> = ib.iter_buffer().collect();
+    assert_eq!(refs, vec!{&1,&2,&3});
+    assert_eq!(ib.len(), 3);
+}
+
+#[test]
+fn test26() {
+    let mut ib = IteratorBuffer::new(1..10, 1);
+    ib.fill_while(|b| b.iter().sum::<i32>() <= 10);
+    assert_eq!(ib.buffer(), &[1,2,3,4,5]);
+    ib.fill_while(|_| true);
+    assert_eq!(ib.len(), 9);
+    assert!(ib.is_closing());
+}
+
+#[test]
+fn test27() {
+    let mut ib = IteratorBuffer::new(0..4, 2);
+    ib.swap(0, 2);
+    assert_eq!(ib.pop(), Some(2));
+    assert_eq!(ib.pop(), Some(1));
+    assert_eq!(ib.pop(), Some(0));
+    assert_eq!(ib.pop(), Some(3));
+}
+
+#[test]
+fn test28() {
+    let mut ib = IteratorBuffer::new(1..6, 2);
+    assert_eq!(ib.pop(), Some(1));
+    assert_eq!(ib.fold_buffer(0, |acc, x| acc + x), 2 + 3 + 4);
+    assert_eq!(ib.len(), 3);
+}
+
+#[test]
+fn test29() {
+    let mut ib = IteratorBuffer::new(0..1, 2);
+    assert_eq!(ib.try_pop(), Ok(0));
+    assert_eq!(ib.try_pop(), Err(BufferEmpty));
+    assert_eq!(BufferEmpty.to_string(), "iterator buffer is empty");
+}
+
+#[test]
+fn test30() {
+    let mut ib = IteratorBuffer::new(0..6, 2);
+    assert_eq!(ib.pop(), Some(0));
+    assert_eq!(ib.pop(), Some(1));
+    assert_eq!(ib.collect_remaining(), vec!{2,3,4,5});
+    let mut ib = IteratorBuffer::new(0..2, 2);
+    assert_eq!(ib.pop(), Some(0));
+    assert_eq!(ib.collect_remaining(), vec!{1});
+}
+
+#[test]
+fn test31() {
+    let mut ib = IteratorBuffer::new(0..6, 1);
+    assert_eq!(ib.get(1), Some(&1));
+    assert_eq!(ib.get(3), None);
+    assert_eq!(ib.get_or_fill(3), Some(&3));
+    assert_eq!(ib.len(), 4);
+    assert_eq!(ib.get(3), Some(&3));
+    assert_eq!(ib.get_or_fill(6), None);
+    assert!(ib.is_closing());
+}
+
+#[test]
+fn test32() {
+    let mut ib = IteratorBuffer::new(1..6, 3);
+    ib.rotate_buffer(2);
+    assert_eq!(ib.buffer(), &[3,4,1,2]);
+    assert_eq!(ib.pop(), Some(3));
+    assert_eq!(ib.buffer(), &[4,1,2,5]);
+}
+
+#[test]
+fn test33() {
+    let mut ib = IteratorBuffer::new(0..3, 1);
+    assert!(ib.is_opening());
+    assert!(ib.has_more());
+    ib.pop();
+    assert!(ib.has_more());
+    ib.pop();
+    assert!(!ib.has_more());
+    assert_eq!(ib.len(), 1);
+    ib.pop();
+    assert!(!ib.has_more());
+    assert!(ib.is_empty());
+}
+
+#[test]
+fn test34() {
+    let mut ib = IteratorBuffer::new(vec!{3,1,4,1,5,9,2,6}.into_iter(), 2);
+    assert_eq!(ib.align_to(|&n| n > 5), 5);
+    assert_eq!(ib.pop(), Some(9));
+    assert_eq!(ib.align_to(|&n| n > 5), 1);
+    assert_eq!(ib.peek(), Some(&6));
+    assert_eq!(ib.align_to(|&n| n > 6), 1);
+    assert!(ib.is_empty());
+}
+
+#[test]
+fn test35() {
+    let mut ib = IteratorBuffer::new("1234567+8".chars(), 2);
+    assert_eq!(ib.lookahead_match_len(|c| c.is_ascii_digit()), 7);
+    assert_eq!(ib.len(), 8);
+    assert_eq!(ib.pop(), Some('1'));
+    assert_eq!(ib.lookahead_match_len(|c| *c == '+'), 0);
+    let mut ib = IteratorBuffer::new("12345".chars(), 2);
+    assert_eq!(ib.lookahead_match_len(|c| c.is_ascii_digit()), 5);
+}
+
+#[test]
+fn test36() {
+    let mut ib = IteratorBuffer::new("bcd".chars(), 1);
+    assert_eq!(ib.compare_prefix(&['b','c','d']), Ordering::Equal);
+    assert_eq!(ib.compare_prefix(&['b','c']), Ordering::Equal);
+    assert_eq!(ib.compare_prefix(&['b','b','z']), Ordering::Greater);
+    assert_eq!(ib.compare_prefix(&['c']), Ordering::Less);
+    assert_eq!(ib.compare_prefix(&['b','c','d','e']), Ordering::Less);
+    assert_eq!(ib.compare_prefix(&[]), Ordering::Equal);
+}
+
+#[test]
+fn test37() {
+    let ib = IteratorBuffer::new("ABABC".chars(), 1);
+    let (_, ib) = prefix_and_rest(ib, &['A','B']).ok().unwrap();
+    let (len, ib) = prefix_and_rest(ib, &['A','B']).ok().unwrap();
+    assert_eq!(len, 2);
+    let mut ib = prefix_and_rest(ib, &['A','B']).err().unwrap();
+    assert_eq!(ib.pop(), Some('C'));
+}
+
+#[test]
+fn test38() {
+    let mut ib = IteratorBuffer::new(vec!{1,1,0,0,0,5}.into_iter(), 1);
+    ib.retain_buffer(|n| *n == 0);
+    assert!(ib.is_empty());
+    assert_eq!(ib.trim_start(|n| *n == 0), 3);
+    assert_eq!(ib.peek(), Some(&5));
+}
+
+#[test]
+fn test39() {
+    let mut ib = IteratorBuffer::new(vec!{1,1,0,0,0,5,7,8,9}.into_iter(), 1);
+    ib.retain_buffer(|n| *n == 0);
+    assert_eq!(ib.read_while(|n| *n == 0), vec!{0,0,0});
+    ib.retain_buffer(|_| false);
+    assert_eq!(ib.drain_until(|n| *n == 9), vec!{8});
+    assert_eq!(ib.pop(), Some(9));
+}
+
+#[test]
+fn test40() {
+    let mut ib = IteratorBuffer::new(0..3, 1);
+    assert_eq!(ib.get_or_fill(usize::MAX), None);
+    assert_eq!(ib.len(), 3);
+}
@@ -0,0 +1,406 @@
+//! Iterators over the elements of an iterator, broken into chunks.
+
+use alloc::vec::Vec;
+use core::ops::Add;
+
+pub struct ChunksExact<I> where I: Iterator {
+    iterator:  I,
+    size:      usize,
+    remainder: Vec<I::Item>,
+}
+
+impl<I> ChunksExact<I> where I: Iterator {
+
+    /// The final elements that did not fill a complete chunk. This
+    /// is empty until the iterator has returned None.
+    pub fn remainder(&self) -> &[I::Item] {
+        &self.remainder
+    }
+}
+
+impl<I> Iterator for ChunksExact<I> where I: Iterator {
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk: Vec<I::Item> = self.iterator.by_ref().take(self.size).collect();
+        if chunk.len() == self.size {
+            Some(chunk)
+        } else {
+            if !chunk.is_empty() {
+                self.remainder = chunk;
+            }
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lo, hi) = self.iterator.size_hint();
+        (lo / self.size, hi.map(|hi| hi / self.size))
+    }
+}
+
+impl<I> ExactSizeIterator for ChunksExact<I> where I: ExactSizeIterator { }
+
+/// Iterate over chunks of `size` elements of the argument
+/// iterator. Like `slice::chunks_exact`, a short final chunk is not
+/// yielded; it is available from `remainder` afterwards. Panics if
+/// `size` is 0.
+pub fn chunks_exact<I>(it: I, size: usize) -> ChunksExact<I> where I: Iterator {
+    assert!(size != 0, "chunk size must be non-zero");
+    ChunksExact {
+        iterator:  it,
+        size,
+        remainder: Vec::new(),
+    }
+}
+
+pub struct ChunkByWeight<I,F,W> where I: Iterator {
+    iterator: I,
+    max:      W,
+    weight:   F,
+    pending:  Option<(I::Item,W)>,
+}
+
+impl<I,F,W> Iterator for ChunkByWeight<I,F,W>
+    where I: Iterator, F: FnMut(&I::Item) -> W, W: Add<Output=W> + PartialOrd + Clone {
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (first, mut total) = match self.pending.take() {
+            Some(pending) => pending,
+            None          => {
+                let item = self.iterator.next()?;
+                let w = (self.weight)(&item);
+                (item, w)
+            }
+        };
+        let mut chunk = vec!{first};
+        for item in self.iterator.by_ref() {
+            let w = (self.weight)(&item);
+            let sum = total.clone() + w.clone();
+            if sum > self.max {
+                self.pending = Some((item, w));
+                break;
+            }
+            total = sum;
+            chunk.push(item);
+        }
+        Some(chunk)
+    }
+}
+
+/// Iterate over chunks of the argument iterator, where each chunk
+/// holds as many adjacent elements as fit without their total
+/// `weight` exceeding `max`. An element heavier than `max` forms a
+/// chunk by itself.
+pub fn chunk_by_weight<I,F,W>(it: I, max: W, weight: F) -> ChunkByWeight<I,F,W>
+    where I: Iterator, F: FnMut(&I::Item) -> W, W: Add<Output=W> + PartialOrd + Clone {
+    ChunkByWeight {
+        iterator: it,
+        max,
+        weight,
+        pending:  None,
+    }
+}
+
+pub struct SplitInclusive<I,P> {
+    iterator: I,
+    pred:     P,
+}
+
+impl<I,P> Iterator for SplitInclusive<I,P> where I: Iterator, P: FnMut(&I::Item) -> bool {
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut segment = Vec::new();
+        for item in self.iterator.by_ref() {
+            let end = (self.pred)(&item);
+            segment.push(item);
+            if end {
+                break;
+            }
+        }
+        if segment.is_empty() { None } else { Some(segment) }
+    }
+}
+
+/// Iterate over segments of the argument iterator, each ending with
+/// an element satisfying `pred`, which is kept. The final segment
+/// does not end with such an element if the iterator does not.
+pub fn split_inclusive<I,P>(it: I, pred: P) -> SplitInclusive<I,P>
+    where I: Iterator, P: FnMut(&I::Item) -> bool {
+    SplitInclusive {
+        iterator: it,
+        pred,
+    }
+}
+
+pub struct ChunkPairs<I> where I: Iterator {
+    iterator:  I,
+    remainder: Option<I::Item>,
+}
+
+impl<I> ChunkPairs<I> where I: Iterator {
+
+    /// The final element, if the iterator had an odd number of
+    /// elements. This is empty until the iterator has returned None.
+    pub fn remainder(&self) -> &[I::Item] {
+        self.remainder.as_slice()
+    }
+}
+
+impl<I> Iterator for ChunkPairs<I> where I: Iterator {
+    type Item = (I::Item,I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.iterator.next()?;
+        match self.iterator.next() {
+            Some(second) => Some((first, second)),
+            None         => { self.remainder = Some(first); None }
+        }
+    }
+}
+
+/// Iterate over non-overlapping pairs of adjacent elements of the
+/// argument iterator. A single final element is not yielded; it is
+/// available from `remainder` afterwards.
+pub fn chunk_pairs<I>(it: I) -> ChunkPairs<I> where I: Iterator {
+    ChunkPairs {
+        iterator:  it,
+        remainder: None,
+    }
+}
+
+pub struct ChunkWhile<I,F> where I: Iterator {
+    iterator: I,
+    pred:     F,
+    pending:  Option<I::Item>,
+}
+
+impl<I,F> Iterator for ChunkWhile<I,F> where I: Iterator, F: FnMut(&I::Item, &I::Item) -> bool {
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.pending.take().or_else(|| self.iterator.next())?;
+        let mut chunk = vec!{first};
+        for item in self.iterator.by_ref() {
+            if (self.pred)(&chunk[chunk.len() - 1], &item) {
+                chunk.push(item);
+            } else {
+                self.pending = Some(item);
+                break;
+            }
+        }
+        Some(chunk)
+    }
+}
+
+/// Iterate over chunks of adjacent elements of the argument
+/// iterator, where `pred(prev, next)` holds for each element `next`
+/// of a chunk and the element `prev` before it. Each element is
+/// compared with its predecessor, not with the first element of the
+/// chunk.
+pub fn chunk_while<I,F>(it: I, pred: F) -> ChunkWhile<I,F>
+    where I: Iterator, F: FnMut(&I::Item, &I::Item) -> bool {
+    ChunkWhile {
+        iterator: it,
+        pred,
+        pending:  None,
+    }
+}
+
+pub struct RevChunks<I> {
+    iterator: I,
+    size:     usize,
+}
+
+impl<I> Iterator for RevChunks<I> where I: DoubleEndedIterator {
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut chunk: Vec<I::Item> = self.iterator.by_ref().rev().take(self.size).collect();
+        if chunk.is_empty() {
+            None
+        } else {
+            chunk.reverse();
+            Some(chunk)
+        }
+    }
+}
+
+/// Iterate over chunks of `size` elements taken from the end of the
+/// argument iterator. The elements within each chunk are in their
+/// original order; the final chunk, from the start of the iterator,
+/// may be short. Panics if `size` is 0.
+pub fn rev_chunks<I>(it: I, size: usize) -> RevChunks<I> where I: DoubleEndedIterator {
+    assert!(size != 0, "chunk size must be non-zero");
+    RevChunks {
+        iterator: it,
+        size,
+    }
+}
+
+pub struct SegmentByThreshold<I,F,W> where I: Iterator {
+    iterator:  I,
+    threshold: W,
+    dist:      F,
+    pending:   Option<I::Item>,
+}
+
+impl<I,F,W> Iterator for SegmentByThreshold<I,F,W>
+    where I: Iterator, F: FnMut(&I::Item, &I::Item) -> W, W: PartialOrd {
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.pending.take().or_else(|| self.iterator.next())?;
+        let mut segment = vec!{first};
+        for item in self.iterator.by_ref() {
+            if (self.dist)(&segment[segment.len() - 1], &item) > self.threshold {
+                self.pending = Some(item);
+                break;
+            }
+            segment.push(item);
+        }
+        Some(segment)
+    }
+}
+
+/// Iterate over segments of adjacent elements of the argument
+/// iterator, starting a new segment wherever `dist(prev, next)`
+/// between an element and its predecessor exceeds `threshold`.
+pub fn segment_by_threshold<I,F,W>(it: I, threshold: W, dist: F) -> SegmentByThreshold<I,F,W>
+    where I: Iterator, F: FnMut(&I::Item, &I::Item) -> W, W: PartialOrd {
+    SegmentByThreshold {
+        iterator: it,
+        threshold,
+        dist,
+        pending:  None,
+    }
+}
+
+pub struct ChunkOnChange<I,S,F> where I: Iterator {
+    iterator: I,
+    state:    S,
+    f:        F,
+    pending:  Option<I::Item>,
+}
+
+impl<I,S,F> Iterator for ChunkOnChange<I,S,F> where I: Iterator, F: FnMut(&mut S, &I::Item) -> bool {
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = match self.pending.take() {
+            Some(item) => item,
+            None       => {
+                let item = self.iterator.next()?;
+                (self.f)(&mut self.state, &item);
+                item
+            }
+        };
+        let mut chunk = vec!{first};
+        for item in self.iterator.by_ref() {
+            if (self.f)(&mut self.state, &item) {
+                self.pending = Some(item);
+                break;
+            }
+            chunk.push(item);
+        }
+        Some(chunk)
+    }
+}
+
+/// Iterate over chunks of adjacent elements of the argument
+/// iterator, starting a new chunk before each element for which
+/// `f(state, element)` returns true. `f` is called once for every
+/// element, in order; its result for the first element is ignored.
+/// The state, starting from `init`, persists across chunks.
+pub fn chunk_on_change<I,S,F>(it: I, init: S, f: F) -> ChunkOnChange<I,S,F>
+    where I: Iterator, F: FnMut(&mut S, &I::Item) -> bool {
+    ChunkOnChange {
+        iterator: it,
+        state:    init,
+        f,
+        pending:  None,
+    }
+}
+
+#[test]
+fn test1() {
+    let mut chunks = chunks_exact(0..7, 3);
+    assert_eq!(chunks.next(), Some(vec!{0,1,2}));
+    assert_eq!(chunks.next(), Some(vec!{3,4,5}));
+    assert_eq!(chunks.next(), None);
+    assert_eq!(chunks.remainder(), &[6]);
+}
+
+#[test]
+fn test2() {
+    let words = vec!{"ab", "cd", "e", "fghijk", "l", "mn"};
+    let chunks: Vec<Vec<&str>> = chunk_by_weight(words.into_iter(), 5, |w| w.len()).collect();
+    assert_eq!(chunks, vec!{vec!{"ab","cd","e"}, vec!{"fghijk"}, vec!{"l","mn"}});
+    let mut empty = chunk_by_weight(Vec::<&str>::new().into_iter(), 5, |w| w.len());
+    assert_eq!(empty.next(), None);
+}
+
+#[test]
+fn test3() {
+    let lines: Vec<Vec<u8>> = split_inclusive(b"ab\nc\nde".iter().cloned(), |&b| b == b'\n').collect();
+    assert_eq!(lines, vec!{b"ab\n".to_vec(), b"c\n".to_vec(), b"de".to_vec()});
+    let lines: Vec<Vec<u8>> = split_inclusive(b"ab\n".iter().cloned(), |&b| b == b'\n').collect();
+    assert_eq!(lines, vec!{b"ab\n".to_vec()});
+}
+
+#[test]
+fn test4() {
+    let mut chunks = chunks_exact(0..7, 3);
+    assert_eq!(chunks.len(), 2);
+    chunks.next();
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks_exact(0..6, 3).len(), 2);
+    assert_eq!(chunks_exact(0..2, 3).len(), 0);
+    assert_eq!(chunks_exact((0..7).filter(|_| true), 3).size_hint(), (0, Some(2)));
+}
+
+#[test]
+fn test5() {
+    let mut pairs = chunk_pairs(vec!{1,2,3,4,5}.into_iter());
+    assert_eq!(pairs.by_ref().collect::<Vec<_>>(), vec!{(1,2), (3,4)});
+    assert_eq!(pairs.remainder(), &[5]);
+    let mut pairs = chunk_pairs(vec!{1,2}.into_iter());
+    assert_eq!(pairs.next(), Some((1,2)));
+    assert_eq!(pairs.next(), None);
+    assert!(pairs.remainder().is_empty());
+}
+
+#[test]
+fn test6() {
+    let runs: Vec<Vec<i32>> = chunk_while(vec!{1,2,3,2,5,6,1}.into_iter(), |prev, next| next > prev).collect();
+    assert_eq!(runs, vec!{vec!{1,2,3}, vec!{2,5,6}, vec!{1}});
+}
+
+#[test]
+fn test7() {
+    let chunks: Vec<Vec<i32>> = rev_chunks(0..7, 3).collect();
+    assert_eq!(chunks, vec!{vec!{4,5,6}, vec!{1,2,3}, vec!{0}});
+    let chunks: Vec<Vec<i32>> = rev_chunks(0..6, 3).collect();
+    assert_eq!(chunks, vec!{vec!{3,4,5}, vec!{0,1,2}});
+}
+
+#[test]
+fn test8() {
+    let segments: Vec<Vec<i32>> = segment_by_threshold(vec!{1,2,10,11,20}.into_iter(), 5, |a, b| b - a).collect();
+    assert_eq!(segments, vec!{vec!{1,2}, vec!{10,11}, vec!{20}});
+    assert_eq!(segment_by_threshold(0..0, 5, |a, b| b - a).next(), None);
+}
+
+#[test]
+fn test9() {
+    let bucket_changed = |sum: &mut i32, n: &i32| {
+        let before = *sum / 10;
+        *sum += *n;
+        *sum / 10 != before
+    };
+    let chunks: Vec<Vec<i32>> = chunk_on_change(vec!{3,4,5,2,9,1}.into_iter(), 0, bucket_changed).collect();
+    assert_eq!(chunks, vec!{vec!{3,4}, vec!{5,2}, vec!{9,1}});
+    assert_eq!(chunk_on_change(0..0, 0, bucket_changed).next(), None);
+}
@@ -0,0 +1,301 @@
+//! Iterators combining the elements of several iterators.
+
+use alloc::vec::Vec;
+use alloc::vec;
+use core::iter::Fuse;
+
+pub struct ChainSep<A,B> where A: Iterator {
+    a:       A,
+    b:       B,
+    sep:     Option<A::Item>,
+    seen_a:  bool,
+    in_b:    bool,
+    pending: Option<A::Item>,
+}
+
+impl<A,B> Iterator for ChainSep<A,B> where A: Iterator, B: Iterator<Item=A::Item> {
+    type Item = A::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.in_b {
+            if let Some(item) = self.a.next() {
+                self.seen_a = true;
+                return Some(item);
+            }
+            self.in_b = true;
+            let first = self.b.next();
+            if self.seen_a && first.is_some() {
+                self.pending = first;
+                return self.sep.take();
+            }
+            return first;
+        }
+        self.pending.take().or_else(|| self.b.next())
+    }
+}
+
+/// Yield the elements of `a`, then `sep`, then the elements of
+/// `b`. The separator is only yielded if both `a` and `b` are
+/// non-empty.
+pub fn chain_sep<A,B>(a: A, b: B, sep: A::Item) -> ChainSep<A,B>
+    where A: Iterator, B: Iterator<Item=A::Item> {
+    ChainSep {
+        a,
+        b,
+        sep:     Some(sep),
+        seen_a:  false,
+        in_b:    false,
+        pending: None,
+    }
+}
+
+/// The iterator an element of `tagged_interleave` came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Source {
+    Left,
+    Right,
+}
+
+pub struct TaggedInterleave<A,B> {
+    a:      Fuse<A>,
+    b:      Fuse<B>,
+    a_next: bool,
+}
+
+impl<A,B> Iterator for TaggedInterleave<A,B> where A: Iterator, B: Iterator<Item=A::Item> {
+    type Item = (Source,A::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let a_next = self.a_next;
+        self.a_next = !a_next;
+        if a_next {
+            self.a.next().map(|item| (Source::Left, item))
+                .or_else(|| self.b.next().map(|item| (Source::Right, item)))
+        } else {
+            self.b.next().map(|item| (Source::Right, item))
+                .or_else(|| self.a.next().map(|item| (Source::Left, item)))
+        }
+    }
+}
+
+/// Alternate between the elements of `a` and `b`, starting with `a`,
+/// tagging each with the iterator it came from. When one iterator is
+/// exhausted, the remaining elements of the other follow.
+pub fn tagged_interleave<A,B>(a: A, b: B) -> TaggedInterleave<A,B>
+    where A: Iterator, B: Iterator<Item=A::Item> {
+    TaggedInterleave {
+        a:      a.fuse(),
+        b:      b.fuse(),
+        a_next: true,
+    }
+}
+
+pub struct ZipAll<I> {
+    iters: Vec<I>,
+    done:  bool,
+}
+
+impl<I> Iterator for ZipAll<I> where I: Iterator {
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let row: Option<Vec<I::Item>> = self.iters.iter_mut().map(|it| it.next()).collect();
+        self.done = row.is_none();
+        row
+    }
+}
+
+/// Yield a Vec holding the next element of each of `iters`, until
+/// any of them is exhausted. With no iterators, nothing is yielded.
+pub fn zip_all<I>(iters: Vec<I>) -> ZipAll<I> where I: Iterator {
+    ZipAll {
+        done:  iters.is_empty(),
+        iters,
+    }
+}
+
+pub struct InterleaveEvenly<A,T> {
+    main:      A,
+    extras:    vec::IntoIter<T>,
+    main_len:  usize,
+    extra_len: usize,
+    mains:     usize,
+    extra:     usize,
+}
+
+impl<A> Iterator for InterleaveEvenly<A,A::Item> where A: Iterator {
+    type Item = A::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let due = (self.extra + 1) * self.main_len / (self.extra_len + 1);
+        if self.extra < self.extra_len && self.mains >= due {
+            self.extra += 1;
+            return self.extras.next();
+        }
+        match self.main.next() {
+            Some(item) => { self.mains += 1; Some(item) }
+            None       => self.extras.next(),
+        }
+    }
+}
+
+/// Yield the elements of `main`, with the elements of `extras` spread
+/// as evenly as possible among them. The `k` extras divide the `n`
+/// main elements into `k + 1` nearly equal parts, the shorter parts
+/// coming first; when there are more extras than main elements,
+/// several extras are adjacent. `extras` is collected to find its
+/// length.
+pub fn interleave_evenly<A,B>(main: A, extras: B) -> InterleaveEvenly<A,A::Item>
+    where A: ExactSizeIterator, B: IntoIterator<Item=A::Item> {
+    let extras: Vec<A::Item> = extras.into_iter().collect();
+    InterleaveEvenly {
+        main_len:  main.len(),
+        extra_len: extras.len(),
+        main,
+        extras:    extras.into_iter(),
+        mains:     0,
+        extra:     0,
+    }
+}
+
+pub struct ZipWith<A,B,F> {
+    a: A,
+    b: B,
+    f: F,
+}
+
+impl<A,B,F,R> Iterator for ZipWith<A,B,F>
+    where A: Iterator, B: Iterator, F: FnMut(A::Item, B::Item) -> R {
+    type Item = R;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let a = self.a.next()?;
+        let b = self.b.next()?;
+        Some((self.f)(a, b))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_lo, a_hi) = self.a.size_hint();
+        let (b_lo, b_hi) = self.b.size_hint();
+        let hi = match (a_hi, b_hi) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b)             => a.or(b),
+        };
+        (a_lo.min(b_lo), hi)
+    }
+}
+
+/// Yield `f` applied to the corresponding elements of `a` and `b`,
+/// stopping at the end of the shorter, as `a.zip(b).map(...)`
+/// without the intermediate tuple.
+///
+/// ```
+/// use iterator_utilities::combine::zip_with;
+///
+/// let sums: Vec<i32> = zip_with(vec!{1,2,3}.into_iter(), vec!{10,20}.into_iter(), |a, b| a + b).collect();
+/// assert_eq!(sums, vec!{11,22});
+/// ```
+pub fn zip_with<A,B,F,R>(a: A, b: B, f: F) -> ZipWith<A,B,F>
+    where A: Iterator, B: Iterator, F: FnMut(A::Item, B::Item) -> R {
+    ZipWith { a, b, f }
+}
+
+pub struct CartesianPower<T> {
+    pool:    Vec<T>,
+    indices: Option<Vec<usize>>,
+}
+
+impl<T> Iterator for CartesianPower<T> where T: Clone {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pool = &self.pool;
+        let indices = self.indices.as_mut()?;
+        let res = indices.iter().map(|&i| pool[i].clone()).collect();
+        let mut done = true;
+        for i in indices.iter_mut().rev() {
+            *i += 1;
+            if *i < pool.len() {
+                done = false;
+                break;
+            }
+            *i = 0;
+        }
+        if done {
+            self.indices = None;
+        }
+        Some(res)
+    }
+}
+
+/// Yield every sequence of `k` elements of the argument iterator,
+/// drawn with repetition, in lexicographic order of position: `n^k`
+/// Vecs for `n` elements. The elements are buffered first, so the
+/// iterator must be finite. With `k` of 0, a single empty Vec is
+/// yielded.
+pub fn cartesian_power<I>(it: I, k: usize) -> CartesianPower<I::Item> where I: Iterator, I::Item: Clone {
+    let pool: Vec<I::Item> = it.collect();
+    let indices = if pool.is_empty() && k > 0 { None } else { Some(vec!{0; k}) };
+    CartesianPower { pool, indices }
+}
+
+#[test]
+fn test1() {
+    let joined: Vec<i32> = chain_sep(0..2, 5..7, -1).collect();
+    assert_eq!(joined, vec!{0,1,-1,5,6});
+    let joined: Vec<i32> = chain_sep(0..2, 0..0, -1).collect();
+    assert_eq!(joined, vec!{0,1});
+    let joined: Vec<i32> = chain_sep(0..0, 5..7, -1).collect();
+    assert_eq!(joined, vec!{5,6});
+    let joined: Vec<i32> = chain_sep(0..0, 0..0, -1).collect();
+    assert!(joined.is_empty());
+}
+
+#[test]
+fn test2() {
+    use self::Source::{Left,Right};
+
+    let merged: Vec<(Source,i32)> = tagged_interleave(0..2, 10..14).collect();
+    assert_eq!(merged, vec!{(Left,0), (Right,10), (Left,1), (Right,11), (Right,12), (Right,13)});
+    let merged: Vec<(Source,i32)> = tagged_interleave(0..3, 10..11).collect();
+    assert_eq!(merged, vec!{(Left,0), (Right,10), (Left,1), (Left,2)});
+}
+
+#[test]
+fn test3() {
+    use core::ops::Range;
+
+    let rows: Vec<Vec<i32>> = zip_all(vec!{0..3, 10..15, 20..24}).collect();
+    assert_eq!(rows, vec!{vec!{0,10,20}, vec!{1,11,21}, vec!{2,12,22}});
+    assert_eq!(zip_all(Vec::<Range<i32>>::new()).next(), None);
+}
+
+#[test]
+fn test4() {
+    let mixed: Vec<i32> = interleave_evenly(1..7, vec!{-1,-2}).collect();
+    assert_eq!(mixed, vec!{1,2,-1,3,4,-2,5,6});
+    let mixed: Vec<i32> = interleave_evenly(1..3, vec!{-1,-2,-3}).collect();
+    assert_eq!(mixed, vec!{-1,1,-2,-3,2});
+    let mixed: Vec<i32> = interleave_evenly(1..1, vec!{-1}).collect();
+    assert_eq!(mixed, vec!{-1});
+}
+
+#[test]
+fn test5() {
+    let products = zip_with(1..4, 4..10, |a, b| a * b);
+    assert_eq!(products.size_hint(), (3, Some(3)));
+    assert_eq!(products.collect::<Vec<_>>(), vec!{4,10,18});
+    assert_eq!(zip_with(0..0, 0..3, |a, b| a + b).next(), None);
+}
+
+#[test]
+fn test6() {
+    let pairs: Vec<Vec<i32>> = cartesian_power(0..2, 2).collect();
+    assert_eq!(pairs, vec!{vec!{0,0}, vec!{0,1}, vec!{1,0}, vec!{1,1}});
+    assert_eq!(cartesian_power(0..3, 3).count(), 27);
+    assert_eq!(cartesian_power(0..3, 0).collect::<Vec<_>>(), vec!{Vec::<i32>::new()});
+    assert_eq!(cartesian_power(0..0, 2).next(), None);
+}
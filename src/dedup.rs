@@ -0,0 +1,218 @@
+//! Iterators collapsing runs of adjacent elements.
+
+#[cfg(test)]
+use alloc::vec::Vec;
+use core::ops::Range;
+
+pub struct DedupWithCount<I> where I: Iterator {
+    iterator: I,
+    pending:  Option<I::Item>,
+}
+
+impl<I> Iterator for DedupWithCount<I> where I: Iterator, I::Item: PartialEq {
+    type Item = (usize,I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.pending.take().or_else(|| self.iterator.next())?;
+        let mut count = 1;
+        for item in self.iterator.by_ref() {
+            if item == first {
+                count += 1;
+            } else {
+                self.pending = Some(item);
+                break;
+            }
+        }
+        Some((count, first))
+    }
+}
+
+/// Yield the first element of each run of adjacent equal elements of
+/// the argument iterator, with the length of the run.
+///
+/// ```
+/// use iterator_utilities::dedup::dedup_with_count;
+///
+/// let runs: Vec<(usize,i32)> = dedup_with_count(vec!{1,1,2,3,3,3}.into_iter()).collect();
+/// assert_eq!(runs, vec!{(2,1), (1,2), (3,3)});
+/// ```
+pub fn dedup_with_count<I>(it: I) -> DedupWithCount<I> where I: Iterator, I::Item: PartialEq {
+    DedupWithCount {
+        iterator: it,
+        pending:  None,
+    }
+}
+
+pub struct DedupByKey<I,F,K> {
+    iterator: I,
+    key:      F,
+    last:     Option<K>,
+}
+
+impl<I,F,K> Iterator for DedupByKey<I,F,K> where I: Iterator, F: FnMut(&I::Item) -> K, K: PartialEq {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let item = self.iterator.next()?;
+            let key = (self.key)(&item);
+            if self.last.as_ref() != Some(&key) {
+                self.last = Some(key);
+                return Some(item);
+            }
+        }
+    }
+}
+
+/// Yield the first element of each run of adjacent elements of the
+/// argument iterator having equal keys. Only the key of the last
+/// element is kept.
+pub fn dedup_by_key<I,F,K>(it: I, key: F) -> DedupByKey<I,F,K>
+    where I: Iterator, F: FnMut(&I::Item) -> K, K: PartialEq {
+    DedupByKey {
+        iterator: it,
+        key,
+        last:     None,
+    }
+}
+
+pub struct RunRanges<I,F> where I: Iterator {
+    iterator: I,
+    same:     F,
+    index:    usize,
+    pending:  Option<I::Item>,
+}
+
+impl<I,F> Iterator for RunRanges<I,F> where I: Iterator, F: FnMut(&I::Item, &I::Item) -> bool {
+    type Item = (Range<usize>,I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.pending.take().or_else(|| self.iterator.next())?;
+        let start = self.index;
+        self.index += 1;
+        for item in self.iterator.by_ref() {
+            if (self.same)(&first, &item) {
+                self.index += 1;
+            } else {
+                self.pending = Some(item);
+                break;
+            }
+        }
+        Some((start..self.index, first))
+    }
+}
+
+/// Yield the first element of each run of adjacent elements of the
+/// argument iterator that are equivalent to it under `same`, with
+/// the range of indices of the run.
+pub fn run_ranges<I,F>(it: I, same: F) -> RunRanges<I,F>
+    where I: Iterator, F: FnMut(&I::Item, &I::Item) -> bool {
+    RunRanges {
+        iterator: it,
+        same,
+        index:    0,
+        pending:  None,
+    }
+}
+
+pub struct MergeAdjacent<I,F> where I: Iterator {
+    iterator: I,
+    f:        F,
+    pending:  Option<I::Item>,
+}
+
+impl<I,F> Iterator for MergeAdjacent<I,F> where I: Iterator, F: FnMut(&I::Item, &I::Item) -> Option<I::Item> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut acc = self.pending.take().or_else(|| self.iterator.next())?;
+        for item in self.iterator.by_ref() {
+            match (self.f)(&acc, &item) {
+                Some(merged) => acc = merged,
+                None         => { self.pending = Some(item); break; }
+            }
+        }
+        Some(acc)
+    }
+}
+
+/// Merge adjacent elements of the argument iterator. When
+/// `f(prev, cur)` returns `Some(merged)`, `merged` replaces both and
+/// is in turn merged with the following element; when it returns
+/// None, `prev` is yielded.
+pub fn merge_adjacent<I,F>(it: I, f: F) -> MergeAdjacent<I,F>
+    where I: Iterator, F: FnMut(&I::Item, &I::Item) -> Option<I::Item> {
+    MergeAdjacent {
+        iterator: it,
+        f,
+        pending:  None,
+    }
+}
+
+pub struct DedupKeepLast<I,F> where I: Iterator {
+    iterator: I,
+    same:     F,
+    pending:  Option<I::Item>,
+}
+
+impl<I,F> Iterator for DedupKeepLast<I,F> where I: Iterator, F: FnMut(&I::Item, &I::Item) -> bool {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut last = self.pending.take().or_else(|| self.iterator.next())?;
+        for item in self.iterator.by_ref() {
+            if (self.same)(&last, &item) {
+                last = item;
+            } else {
+                self.pending = Some(item);
+                break;
+            }
+        }
+        Some(last)
+    }
+}
+
+/// Yield the last element of each run of adjacent elements of the
+/// argument iterator, where `same(prev, next)` holds for each
+/// element of a run and the element before it. One element past
+/// each run is read and held until the next call.
+pub fn dedup_keep_last<I,F>(it: I, same: F) -> DedupKeepLast<I,F>
+    where I: Iterator, F: FnMut(&I::Item, &I::Item) -> bool {
+    DedupKeepLast {
+        iterator: it,
+        same,
+        pending:  None,
+    }
+}
+
+#[test]
+fn test1() {
+    let words = vec!{"a", "b", "cc", "dd", "e", "ff"};
+    let deduped: Vec<&str> = dedup_by_key(words.into_iter(), |w| w.len()).collect();
+    assert_eq!(deduped, vec!{"a", "cc", "e", "ff"});
+}
+
+#[test]
+fn test2() {
+    let runs: Vec<(Range<usize>,u32)> = run_ranges(0.., |l, r| l / 3 == r / 3).take(3).collect();
+    assert_eq!(runs, vec!{(0..3, 0), (3..6, 3), (6..9, 6)});
+    let runs: Vec<(Range<usize>,char)> = run_ranges("aab".chars(), |l, r| l == r).collect();
+    assert_eq!(runs, vec!{(0..2, 'a'), (2..3, 'b')});
+}
+
+#[test]
+fn test3() {
+    let ranges = vec!{(1,3), (2,5), (5,6), (8,9), (9,12), (14,15)};
+    let merged: Vec<(i32,i32)> = merge_adjacent(ranges.into_iter(), |&(s1,e1), &(s2,e2)| {
+        if s2 <= e1 { Some((s1, e1.max(e2))) } else { None }
+    }).collect();
+    assert_eq!(merged, vec!{(1,6), (8,12), (14,15)});
+}
+
+#[test]
+fn test4() {
+    let pairs = vec!{(1,'a'), (1,'b'), (2,'c')};
+    let last: Vec<(i32,char)> = dedup_keep_last(pairs.into_iter(), |l, r| l.0 == r.0).collect();
+    assert_eq!(last, vec!{(1,'b'), (2,'c')});
+    assert_eq!(dedup_keep_last(0..0, |l, r| l == r).next(), None);
+}
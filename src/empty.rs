@@ -1,16 +1,89 @@
 //! An iterator that contains no elements.
 
-use std::marker::PhantomData;
+use core::iter;
+use core::marker::PhantomData;
 
 pub struct Empty<Elt> {
     phantom: PhantomData<Elt>
 }
 
 impl<Elt> Empty<Elt> {
-    pub fn new() -> Empty<Elt> { Empty { phantom: PhantomData } }
+    /// An empty iterator, usable in constant expressions.
+    pub const EMPTY: Empty<Elt> = Empty { phantom: PhantomData };
+
+    pub const fn new() -> Empty<Elt> { Empty { phantom: PhantomData } }
+
+    /// Convert to the standard library's empty iterator.
+    pub fn into_std(self) -> iter::Empty<Elt> { iter::empty() }
+}
+
+impl<Elt> From<iter::Empty<Elt>> for Empty<Elt> {
+    fn from(_: iter::Empty<Elt>) -> Empty<Elt> { Empty::new() }
+}
+
+impl<Elt> Default for Empty<Elt> {
+    fn default() -> Empty<Elt> { Empty::new() }
 }
 
 impl<Elt> Iterator for Empty<Elt> {
     type Item = Elt;
     fn next(&mut self) -> Option<Self::Item> { None }
 }
+
+/// Return true if the argument iterator yields no elements. At most
+/// one element is read, but the iterator is taken by value: testing
+/// an iterator consumes it.
+pub fn is_empty_iter<I>(mut it: I) -> bool where I: Iterator {
+    it.next().is_none()
+}
+
+pub struct EmptyIf<I> {
+    iterator: Option<I>,
+}
+
+impl<I> Iterator for EmptyIf<I> where I: Iterator {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iterator.as_mut()?.next()
+    }
+}
+
+/// Yield the elements of the argument iterator, or nothing if `cond`
+/// is true.
+pub fn empty_if<I>(it: I, cond: bool) -> EmptyIf<I> where I: Iterator {
+    EmptyIf { iterator: if cond { None } else { Some(it) } }
+}
+
+#[test]
+fn test1() {
+    let mut e: Empty<u8> = Empty::from(iter::empty());
+    assert_eq!(e.next(), None);
+    let mut std_e = e.into_std();
+    assert_eq!(std_e.next(), None);
+    let mut e: Empty<u8> = std_e.into();
+    assert_eq!(e.next(), None);
+}
+
+#[test]
+fn test2() {
+    const E: Empty<u32> = Empty::new();
+    static S: Empty<u32> = Empty::EMPTY;
+    assert_eq!(E.count(), 0);
+    assert_eq!(S.size_hint(), (0, None));
+    let mut e = Empty::<u32>::EMPTY;
+    assert_eq!(e.next(), None);
+}
+
+#[test]
+fn test3() {
+    assert!(is_empty_iter(Empty::<u8>::new()));
+    assert!(!is_empty_iter(0..3));
+    assert!(is_empty_iter(0..0));
+}
+
+#[test]
+fn test4() {
+    assert_eq!(empty_if(0..3, false).count(), 3);
+    assert_eq!(empty_if(0..3, true).next(), None);
+}
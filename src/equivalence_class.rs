@@ -1,26 +1,27 @@
 //! An iterator iterator over the equivalence classes in a slice.
 
-use std;
-use std::iter::Iterator;
-use std::ops::Fn;
+use alloc::vec::Vec;
+use core;
+use core::fmt;
+use core::iter::Iterator;
+use core::mem;
+use core::ops::Fn;
 
 pub struct EqClIter<'t,T,F> where T:'t, F:Fn(&'t T,&'t T)->bool {
     vect: &'t [T],
     pred: F,
     last: usize,
+    peek: Option<usize>,
 }
 
 impl<'t,T,F> Iterator for EqClIter<'t,T,F> where T:'t, F:Fn(&'t T,&'t T)->bool {
-    type Item = std::slice::Iter<'t,T>;
+    type Item = core::slice::Iter<'t,T>;
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.last >= self.vect.len() {
             None
         } else {
-            let mut i = self.last;
-            while i < self.vect.len() && (self.pred)(&self.vect[self.last], &self.vect[i]) {
-                i += 1;
-            }
+            let i = self.peek.take().unwrap_or_else(|| self.class_end());
             let iter = self.vect[self.last..i].iter();
             self.last = i;
             Some(iter)
@@ -28,6 +29,72 @@ impl<'t,T,F> Iterator for EqClIter<'t,T,F> where T:'t, F:Fn(&'t T,&'t T)->bool {
     }
 }
 
+impl<'t,T,F> EqClIter<'t,T,F> where T:'t, F:Fn(&'t T,&'t T)->bool {
+
+    /// Yield each remaining equivalence class as a Vec of copies of
+    /// its elements. Classes are still found lazily, one per call to
+    /// `next`.
+    pub fn owned(self) -> OwnedClasses<'t,T,F> where T: Clone {
+        OwnedClasses { classes: self }
+    }
+
+    /// Skip `n` equivalence classes and return the next, as
+    /// `Iterator::nth`. The skipped classes are only scanned for
+    /// their boundaries.
+    pub fn nth_group(&mut self, n: usize) -> Option<core::slice::Iter<'t,T>> {
+        for _ in 0..n {
+            if self.last >= self.vect.len() { return None; }
+            self.last = self.peek.take().unwrap_or_else(|| self.class_end());
+        }
+        self.next()
+    }
+
+    /// Return the next equivalence class without advancing past
+    /// it. Its end is remembered, so the following `next` does not
+    /// scan the class again.
+    pub fn peek_group(&mut self) -> Option<&'t [T]> {
+        if self.last >= self.vect.len() { return None; }
+        let end = match self.peek {
+            Some(end) => end,
+            None      => self.class_end(),
+        };
+        self.peek = Some(end);
+        Some(&self.vect[self.last..end])
+    }
+
+    /// Return the index just past the class beginning at `last`.
+    fn class_end(&self) -> usize {
+        let mut i = self.last;
+        while i < self.vect.len() && (self.pred)(&self.vect[self.last], &self.vect[i]) {
+            i += 1;
+        }
+        i
+    }
+}
+
+pub struct OwnedClasses<'t,T,F> where T:'t, F:Fn(&'t T,&'t T)->bool {
+    classes: EqClIter<'t,T,F>,
+}
+
+impl<'t,T,F> Iterator for OwnedClasses<'t,T,F> where T:'t + Clone, F:Fn(&'t T,&'t T)->bool {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.classes.next().map(|class| class.cloned().collect())
+    }
+}
+
+impl<'t,T,F> fmt::Debug for EqClIter<'t,T,F> where T:'t + fmt::Debug, F:Fn(&'t T,&'t T)->bool {
+
+    /// Show the remaining, not yet grouped, part of the slice.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("EqClIter")
+            .field("remaining", &&self.vect[self.last..])
+            .field("last", &self.last)
+            .finish()
+    }
+}
+
 /// Iterate over sub-slices of the argument slice, where the elements
 /// of each sub-slice are adjacent and equivalent under the predicate.
 ///
@@ -54,8 +121,202 @@ pub fn equivalence_classes<'t,T,F>(slice: &'t Vec<T>, predicate: F) -> EqClIter<
             vect: slice,
             pred: predicate,
             last: 0,
+            peek: None,
+        }
+    }
+
+pub struct EqClIterMut<'t,T,F> where T:'t, F:Fn(&T,&T)->bool {
+    rest: &'t mut [T],
+    pred: F,
+}
+
+impl<'t,T,F> Iterator for EqClIterMut<'t,T,F> where T:'t, F:Fn(&T,&T)->bool {
+    type Item = &'t mut [T];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            None
+        } else {
+            let mut i = 0;
+            while i < self.rest.len() && (self.pred)(&self.rest[0], &self.rest[i]) {
+                i += 1;
+            }
+            let rest = mem::take(&mut self.rest);
+            let (class, rest) = rest.split_at_mut(i);
+            self.rest = rest;
+            Some(class)
+        }
+    }
+}
+
+/// Iterate over mutable sub-slices of the argument slice, where the
+/// elements of each sub-slice are adjacent and equivalent under the
+/// predicate. The classes are determined before each sub-slice is
+/// yielded, so changes to a class do not affect its boundaries.
+pub fn equivalence_classes_mut<'t,T,F>(slice: &'t mut [T], predicate: F) -> EqClIterMut<'t,T,F>
+    where F: Fn(&T,&T)->bool {
+        EqClIterMut {
+            rest: slice,
+            pred: predicate,
+        }
+    }
+
+pub struct EqClIterIndexed<'t,T,F> where T:'t, F:Fn(usize,&T,usize,&T)->bool {
+    vect: &'t [T],
+    pred: F,
+    last: usize,
+}
+
+impl<'t,T,F> Iterator for EqClIterIndexed<'t,T,F> where T:'t, F:Fn(usize,&T,usize,&T)->bool {
+    type Item = core::slice::Iter<'t,T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.last >= self.vect.len() {
+            None
+        } else {
+            let mut i = self.last;
+            while i < self.vect.len() && (self.pred)(self.last, &self.vect[self.last], i, &self.vect[i]) {
+                i += 1;
+            }
+            let iter = self.vect[self.last..i].iter();
+            self.last = i;
+            Some(iter)
+        }
+    }
+}
+
+/// Iterate over sub-slices of the argument slice, as
+/// `equivalence_classes`, but the predicate also receives the index
+/// of each element: `predicate(i, first, j, current)` compares the
+/// first element of the class, at index `i`, with the element at
+/// index `j`.
+pub fn equivalence_classes_indexed<'t,T,F>(slice: &'t [T], predicate: F) -> EqClIterIndexed<'t,T,F>
+    where F: Fn(usize,&T,usize,&T)->bool {
+        EqClIterIndexed {
+            vect: slice,
+            pred: predicate,
+            last: 0,
+        }
+    }
+
+/// Find the runs of adjacent elements of the argument slice that are
+/// equivalent under the predicate, returning the first element of
+/// each run with the indices of all of the run's elements.
+pub fn runs_with_indices<T,F>(slice: &[T], same: F) -> Vec<(T,Vec<usize>)>
+    where T: Clone, F: Fn(&T,&T)->bool {
+        let mut start = 0;
+        equivalence_classes_indexed(slice, |_,l,_,r| same(l,r))
+            .map(|class| {
+                let end = start + class.len();
+                let run = (slice[start].clone(), (start..end).collect());
+                start = end;
+                run
+            })
+            .collect()
+    }
+
+pub struct Rle<'t,T> where T:'t {
+    rest: &'t [T],
+}
+
+impl<'t,T> Iterator for Rle<'t,T> where T:'t + PartialEq {
+    type Item = (&'t T,usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.rest.first()?;
+        let len = self.rest.iter().take_while(|t| *t == first).count();
+        self.rest = &self.rest[len..];
+        Some((first, len))
+    }
+}
+
+/// Run-length encode the argument slice, yielding a reference to the
+/// first element of each run of adjacent equal elements, with the
+/// length of the run.
+pub fn rle<'t,T>(slice: &'t [T]) -> Rle<'t,T> where T: PartialEq {
+    Rle { rest: slice }
+}
+
+/// Chain the equivalence classes back into a single iterator over
+/// the elements, in their original order.
+pub fn flatten_classes<'t,T,F>(classes: EqClIter<'t,T,F>) -> impl Iterator<Item=&'t T>
+    where T:'t, F:Fn(&'t T,&'t T)->bool {
+        classes.flatten()
+    }
+
+/// Iterate over the runs of adjacent equal elements of the argument
+/// slice, as `equivalence_classes` with equality as the predicate.
+///
+/// ```
+/// use iterator_utilities::equivalence_class::equal_runs;
+///
+/// let runs: Vec<&[i32]> = equal_runs(&[1,1,2,2,2,3]).collect();
+/// assert_eq!(runs, vec!{&[1,1][..], &[2,2,2][..], &[3][..]});
+/// ```
+pub fn equal_runs<T>(slice: &[T]) -> impl Iterator<Item=&[T]>
+    where T: PartialEq {
+        equivalence_classes_indexed(slice, |_,l,_,r| l == r).map(|class| class.as_slice())
+    }
+
+pub struct EqClIterRev<'t,T,F> where T:'t, F:Fn(&T,&T)->bool {
+    vect: &'t [T],
+    pred: F,
+    end:  usize,
+}
+
+impl<'t,T,F> Iterator for EqClIterRev<'t,T,F> where T:'t, F:Fn(&T,&T)->bool {
+    type Item = core::slice::Iter<'t,T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.end == 0 {
+            None
+        } else {
+            let mut i = self.end - 1;
+            while i > 0 && (self.pred)(&self.vect[i - 1], &self.vect[self.end - 1]) {
+                i -= 1;
+            }
+            let iter = self.vect[i..self.end].iter();
+            self.end = i;
+            Some(iter)
         }
     }
+}
+
+/// Iterate over the equivalence classes of the argument slice from
+/// last to first; the elements of each class are still in their
+/// original order. Each element is compared with the last element
+/// of its class, as `predicate(element, last)`, so for a predicate
+/// that is an equivalence relation the classes are those of
+/// `equivalence_classes`, reversed.
+pub fn equivalence_classes_rev<'t,T,F>(slice: &'t [T], predicate: F) -> EqClIterRev<'t,T,F>
+    where F: Fn(&T,&T)->bool {
+        EqClIterRev {
+            vect: slice,
+            pred: predicate,
+            end:  slice.len(),
+        }
+    }
+
+/// Fold each run of adjacent elements of the argument slice having
+/// equal keys, starting from a copy of `init`, returning each run's
+/// key with its result.
+pub fn class_reduce<T,K,F,G,A>(slice: &[T], mut key: F, init: A, mut fold: G) -> Vec<(K,A)>
+    where F: FnMut(&T) -> K, K: PartialEq, G: FnMut(A, &T) -> A, A: Clone {
+        let mut res: Vec<(K,A)> = Vec::new();
+        for t in slice {
+            let k = key(t);
+            match res.pop() {
+                Some((last, acc)) if last == k => {
+                    res.push((last, fold(acc, t)));
+                }
+                prev => {
+                    res.extend(prev);
+                    res.push((k, fold(init.clone(), t)));
+                }
+            }
+        }
+        res
+    }
 
 #[test]
 fn test1() {
@@ -76,3 +337,116 @@ fn test1() {
     } else { panic!("no even iterator"); }
     assert!(eq.next().is_none());
 }
+
+#[test]
+fn test2() {
+    let ns = vec!{0,2,1,3};
+    let mut eq = equivalence_classes(&ns, |l,r| l%2 == r%2 );
+    assert_eq!(format!("{:?}", eq), "EqClIter { remaining: [0, 2, 1, 3], last: 0 }");
+    eq.next();
+    assert_eq!(format!("{:?}", eq), "EqClIter { remaining: [1, 3], last: 2 }");
+}
+
+#[test]
+fn test3() {
+    let mut ns = vec!{0,2,1,3,4,6,5};
+    for (i, class) in equivalence_classes_mut(&mut ns, |l,r| l%2 == r%2 ).enumerate() {
+        if i % 2 == 1 {
+            for n in class.iter_mut() {
+                *n = -*n;
+            }
+        }
+    }
+    assert_eq!(ns, vec!{0,2,-1,-3,4,6,-5});
+}
+
+#[test]
+fn test4() {
+    let ns = vec!{0,2,4,6,8,1,3};
+    let eq: Vec<Vec<&i32>> = equivalence_classes_indexed(&ns, |i,l,j,r| l%2 == r%2 && j - i < 3)
+        .map(|class| class.collect())
+        .collect();
+    assert_eq!(eq, vec!{vec!{&0,&2,&4}, vec!{&6,&8}, vec!{&1,&3}});
+}
+
+#[test]
+fn test5() {
+    let cs = vec!{'a','a','b','a'};
+    assert_eq!(runs_with_indices(&cs, |l,r| l == r),
+               vec!{('a', vec!{0,1}), ('b', vec!{2}), ('a', vec!{3})});
+}
+
+#[test]
+fn test6() {
+    use core::cell::Cell;
+
+    let ns = vec!{0,2,1,3,4};
+    let calls = Cell::new(0);
+    let mut eq = equivalence_classes(&ns, |l,r| { calls.set(calls.get() + 1); l%2 == r%2 }).owned();
+    let even: Vec<i32> = eq.next().unwrap();
+    assert_eq!(even, vec!{0,2});
+    assert_eq!(calls.get(), 3);
+    assert_eq!(eq.collect::<Vec<_>>(), vec!{vec!{1,3}, vec!{4}});
+    assert_eq!(calls.get(), 7);
+}
+
+#[test]
+fn test7() {
+    let runs: Vec<(&u8,usize)> = rle("aaabccd".as_bytes()).collect();
+    assert_eq!(runs, vec!{(&b'a',3), (&b'b',1), (&b'c',2), (&b'd',1)});
+    assert_eq!(rle::<u8>(&[]).next(), None);
+}
+
+#[test]
+fn test8() {
+    let ns = vec!{0,2,1,3,4,5};
+    let flat: Vec<&i32> = flatten_classes(equivalence_classes(&ns, |l,r| l%2 == r%2 )).collect();
+    assert_eq!(flat, ns.iter().collect::<Vec<_>>());
+    let mut eq = equivalence_classes(&ns, |l,r| l%2 == r%2 );
+    eq.next();
+    let rest: Vec<&i32> = flatten_classes(eq).collect();
+    assert_eq!(rest, vec!{&1,&3,&4,&5});
+}
+
+#[test]
+fn test9() {
+    let ns = vec!{0,2,1,3,5,4};
+    let forward: Vec<Vec<&i32>> = equivalence_classes(&ns, |l,r| l%2 == r%2 ).map(|c| c.collect()).collect();
+    let mut backward: Vec<Vec<&i32>> = equivalence_classes_rev(&ns, |l,r| l%2 == r%2 ).map(|c| c.collect()).collect();
+    assert_eq!(backward, vec!{vec!{&4}, vec!{&1,&3,&5}, vec!{&0,&2}});
+    backward.reverse();
+    assert_eq!(backward, forward);
+}
+
+#[test]
+fn test10() {
+    let ns = vec!{1,3,2,4,6,5};
+    assert_eq!(class_reduce(&ns, |n| n % 2, 0, |sum, n| sum + n), vec!{(1,4), (0,12), (1,5)});
+    assert!(class_reduce(&[] as &[i32], |n| n % 2, 0, |sum, n| sum + n).is_empty());
+}
+
+#[test]
+fn test11() {
+    let ns = vec!{1,1,2,3,3,3,4};
+    let mut classes = equivalence_classes(&ns, |l,r| l == r);
+    let third: Vec<&i32> = classes.nth_group(2).unwrap().collect();
+    assert_eq!(third, vec!{&3,&3,&3});
+    let fourth: Vec<&i32> = classes.next().unwrap().collect();
+    assert_eq!(fourth, vec!{&4});
+    assert!(classes.nth_group(0).is_none());
+    assert!(equivalence_classes(&ns, |l,r| l == r).nth_group(4).is_none());
+}
+
+#[test]
+fn test12() {
+    let ns = vec!{1,1,2,3,3};
+    let mut classes = equivalence_classes(&ns, |l,r| l == r);
+    assert_eq!(classes.peek_group(), Some(&[1,1][..]));
+    assert_eq!(classes.peek_group(), Some(&[1,1][..]));
+    let first: Vec<&i32> = classes.next().unwrap().collect();
+    assert_eq!(first, vec!{&1,&1});
+    assert_eq!(classes.peek_group(), Some(&[2][..]));
+    let third: Vec<&i32> = classes.nth_group(1).unwrap().collect();
+    assert_eq!(third, vec!{&3,&3});
+    assert_eq!(classes.peek_group(), None);
+}
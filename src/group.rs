@@ -0,0 +1,127 @@
+//! Functions collecting elements into groups.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::hash::Hash;
+
+/// Group `items` by `key`, returning each distinct key, in order,
+/// with all of the items having that key. The items are sorted by
+/// key and adjacent equal keys are then merged, so the keys need
+/// only be `Ord`, not `Hash`. The sort is unstable: items within a
+/// group are not necessarily in their input order.
+pub fn group_by_sorted<T,K,F>(items: Vec<T>, mut key: F) -> Vec<(K,Vec<T>)>
+    where F: FnMut(&T) -> K, K: Ord {
+    let mut keyed: Vec<(K,T)> = items.into_iter().map(|t| (key(&t), t)).collect();
+    keyed.sort_unstable_by(|l,r| l.0.cmp(&r.0));
+    let mut groups: Vec<(K,Vec<T>)> = Vec::new();
+    for (k, t) in keyed {
+        match groups.last_mut() {
+            Some(&mut (ref last, ref mut group)) if *last == k => group.push(t),
+            _ => groups.push((k, vec!{t})),
+        }
+    }
+    groups
+}
+
+/// Count the elements of the argument iterator falling in each
+/// bucket given by `key`, returning each bucket seen, with its
+/// count, in ascending order of bucket. Buckets with no elements are
+/// not included, so an empty iterator produces an empty result.
+pub fn histogram<I,K,F>(it: I, mut key: F) -> Vec<(K,usize)>
+    where I: Iterator, F: FnMut(&I::Item) -> K, K: Ord {
+    let mut counts = BTreeMap::new();
+    for item in it {
+        *counts.entry(key(&item)).or_insert(0) += 1;
+    }
+    counts.into_iter().collect()
+}
+
+/// Collect the key-value pairs of the argument iterator by key,
+/// returning each distinct key, in the order first seen, with all of
+/// its values, in order. Keys are compared linearly, so they need
+/// only be `Eq`; this suits a small number of distinct keys.
+pub fn collect_grouped<I,K,V>(it: I) -> Vec<(K,Vec<V>)> where I: Iterator<Item=(K,V)>, K: Eq {
+    let mut groups: Vec<(K,Vec<V>)> = Vec::new();
+    for (k, v) in it {
+        match groups.iter_mut().find(|group| group.0 == k) {
+            Some(group) => group.1.push(v),
+            None        => groups.push((k, vec!{v})),
+        }
+    }
+    groups
+}
+
+/// Split the groups yielded by the argument iterator into their keys
+/// and their lists of values, in order: the inverse of
+/// `collect_grouped`.
+pub fn flat_unzip<I,A,B>(it: I) -> (Vec<A>,Vec<Vec<B>>) where I: Iterator<Item=(A,Vec<B>)> {
+    it.unzip()
+}
+
+/// Map each element of the argument iterator to a key-value pair
+/// with `f`, collecting the values by key. The first error returned
+/// by `f` is returned instead, and no more of the iterator is
+/// read. Requires the `std` feature.
+#[cfg(feature = "std")]
+pub fn try_group_map<I,K,V,E,F>(it: I, mut f: F) -> Result<HashMap<K,Vec<V>>,E>
+    where I: Iterator, K: Eq + Hash, F: FnMut(I::Item) -> Result<(K,V),E> {
+    let mut groups: HashMap<K,Vec<V>> = HashMap::new();
+    for item in it {
+        let (k, v) = f(item)?;
+        groups.entry(k).or_default().push(v);
+    }
+    Ok(groups)
+}
+
+#[test]
+fn test1() {
+    let words = vec!{"bb", "a", "ccc", "dd", "e", "fff", "gg"};
+    let mut groups = group_by_sorted(words, |w| w.len());
+    for &mut (_, ref mut group) in groups.iter_mut() {
+        group.sort();
+    }
+    assert_eq!(groups, vec!{(1, vec!{"a","e"}), (2, vec!{"bb","dd","gg"}), (3, vec!{"ccc","fff"})});
+    assert_eq!(group_by_sorted(Vec::<i32>::new(), |&n| n), vec!{});
+}
+
+#[test]
+fn test2() {
+    let ns = vec!{31, 5, 12, 38, 17, 3, 35};
+    assert_eq!(histogram(ns.into_iter(), |n| n / 10), vec!{(0,2), (1,2), (3,3)});
+    assert!(histogram(Vec::<i32>::new().into_iter(), |n| n / 10).is_empty());
+}
+
+#[test]
+fn test3() {
+    let pairs = vec!{("b",1), ("a",2), ("b",3), ("c",4), ("a",5)};
+    assert_eq!(collect_grouped(pairs.into_iter()),
+               vec!{("b", vec!{1,3}), ("a", vec!{2,5}), ("c", vec!{4})});
+    assert!(collect_grouped(Vec::<(i32,i32)>::new().into_iter()).is_empty());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test4() {
+    let parse = |s: &str| s.parse::<i32>().map(|n| (n % 2, n));
+    let groups = try_group_map(vec!{"1","2","3"}.into_iter(), parse).unwrap();
+    assert_eq!(groups[&1], vec!{1,3});
+    assert_eq!(groups[&0], vec!{2});
+    let mut it = vec!{"1","x","3"}.into_iter();
+    assert!(try_group_map(it.by_ref(), parse).is_err());
+    assert_eq!(it.next(), Some("3"));
+}
+
+#[test]
+fn test5() {
+    let pairs = vec!{("b",1), ("a",2), ("b",3)};
+    let (keys, values) = flat_unzip(collect_grouped(pairs.into_iter()).into_iter());
+    assert_eq!(keys, vec!{"b","a"});
+    assert_eq!(values, vec!{vec!{1,3}, vec!{2}});
+    let rezipped: Vec<(&str,i32)> = keys.into_iter().zip(values)
+        .flat_map(|(k, vs)| vs.into_iter().map(move |v| (k, v)))
+        .collect();
+    assert_eq!(collect_grouped(rezipped.into_iter()), vec!{("b", vec!{1,3}), ("a", vec!{2})});
+}
@@ -0,0 +1,282 @@
+//! An extension trait providing the crate's adapters as methods on any iterator.
+
+#[cfg(test)]
+use alloc::vec::Vec;
+use core::ops::Add;
+#[cfg(feature = "std")]
+use std::hash::Hash;
+use chunks::{self, ChunkByWeight, ChunkOnChange, ChunkPairs, ChunkWhile};
+use chunks::{ChunksExact, RevChunks, SegmentByThreshold, SplitInclusive};
+use combine::{self, CartesianPower, ChainSep, InterleaveEvenly, TaggedInterleave, ZipWith};
+use dedup::{self, DedupByKey, DedupKeepLast, DedupWithCount, MergeAdjacent, RunRanges};
+use empty::{self, EmptyIf};
+use result::{self, MapErr, MapOk, MapWhileOk};
+use scan::{self, FillMissing, RepeatLast, RunningMax, RunningMin, ScanIndexed, Scanl, TryScan};
+#[cfg(feature = "std")]
+use scan::CumulativeCounts;
+use select::{self, EveryNthMatching, FlattenOptions, Positions, StepByIndexed};
+use windows::{self, MapWindows2, SlidingReduce, WindowsMap};
+
+/// Methods wrapping the iterator adapters defined elsewhere in the
+/// crate, implemented for every iterator. Each is equivalent to the
+/// free function of the same name, with the iterator as `self`.
+///
+/// Every function in the crate that takes an iterator as its first
+/// argument and returns an iterator is included. Functions consuming
+/// an iterator to a result, such as those in `reduce` and `group`,
+/// are not, nor is `combine::zip_all`, which takes a Vec of
+/// iterators.
+///
+/// The crate has no `chunks`, `windows`, `group_by`, `intersperse`
+/// or `with_position` adapters. The nearest are `chunks_exact`,
+/// which keeps a short final chunk as its `remainder` rather than
+/// yielding it; `windows_map`, which maps each window rather than
+/// yielding it; `chunk_while` and `dedup_by_key`; and `chain_sep`,
+/// which places one separator between two iterators. Nothing
+/// corresponds to `with_position`.
+///
+/// ```
+/// use iterator_utilities::iter_utils::IterUtils;
+///
+/// let mut chunks = (0..10).chunks_exact(3);
+/// assert_eq!(chunks.by_ref().collect::<Vec<_>>(), vec!{vec!{0,1,2}, vec!{3,4,5}, vec!{6,7,8}});
+/// assert_eq!(chunks.remainder(), &[9]);
+/// ```
+pub trait IterUtils: Iterator + Sized {
+
+    /// As `chunks::chunks_exact`.
+    fn chunks_exact(self, size: usize) -> ChunksExact<Self> {
+        chunks::chunks_exact(self, size)
+    }
+
+    /// As `chunks::chunk_by_weight`.
+    fn chunk_by_weight<F,W>(self, max: W, weight: F) -> ChunkByWeight<Self,F,W>
+        where F: FnMut(&Self::Item) -> W, W: Add<Output=W> + PartialOrd + Clone {
+        chunks::chunk_by_weight(self, max, weight)
+    }
+
+    /// As `chunks::split_inclusive`.
+    fn split_inclusive<P>(self, pred: P) -> SplitInclusive<Self,P>
+        where P: FnMut(&Self::Item) -> bool {
+        chunks::split_inclusive(self, pred)
+    }
+
+    /// As `chunks::chunk_pairs`.
+    fn chunk_pairs(self) -> ChunkPairs<Self> {
+        chunks::chunk_pairs(self)
+    }
+
+    /// As `chunks::chunk_while`.
+    fn chunk_while<F>(self, pred: F) -> ChunkWhile<Self,F>
+        where F: FnMut(&Self::Item, &Self::Item) -> bool {
+        chunks::chunk_while(self, pred)
+    }
+
+    /// As `chunks::rev_chunks`.
+    fn rev_chunks(self, size: usize) -> RevChunks<Self> where Self: DoubleEndedIterator {
+        chunks::rev_chunks(self, size)
+    }
+
+    /// As `chunks::segment_by_threshold`.
+    fn segment_by_threshold<F,W>(self, threshold: W, dist: F) -> SegmentByThreshold<Self,F,W>
+        where F: FnMut(&Self::Item, &Self::Item) -> W, W: PartialOrd {
+        chunks::segment_by_threshold(self, threshold, dist)
+    }
+
+    /// As `chunks::chunk_on_change`.
+    fn chunk_on_change<S,F>(self, init: S, f: F) -> ChunkOnChange<Self,S,F>
+        where F: FnMut(&mut S, &Self::Item) -> bool {
+        chunks::chunk_on_change(self, init, f)
+    }
+
+    /// As `combine::chain_sep`.
+    fn chain_sep<B>(self, b: B, sep: Self::Item) -> ChainSep<Self,B>
+        where B: Iterator<Item=Self::Item> {
+        combine::chain_sep(self, b, sep)
+    }
+
+    /// As `combine::tagged_interleave`.
+    fn tagged_interleave<B>(self, b: B) -> TaggedInterleave<Self,B>
+        where B: Iterator<Item=Self::Item> {
+        combine::tagged_interleave(self, b)
+    }
+
+    /// As `combine::interleave_evenly`.
+    fn interleave_evenly<B>(self, extras: B) -> InterleaveEvenly<Self,Self::Item>
+        where Self: ExactSizeIterator, B: IntoIterator<Item=Self::Item> {
+        combine::interleave_evenly(self, extras)
+    }
+
+    /// As `combine::zip_with`.
+    fn zip_with<B,F,R>(self, b: B, f: F) -> ZipWith<Self,B,F>
+        where B: Iterator, F: FnMut(Self::Item, B::Item) -> R {
+        combine::zip_with(self, b, f)
+    }
+
+    /// As `combine::cartesian_power`.
+    fn cartesian_power(self, k: usize) -> CartesianPower<Self::Item> where Self::Item: Clone {
+        combine::cartesian_power(self, k)
+    }
+
+    /// As `dedup::dedup_with_count`.
+    fn dedup_with_count(self) -> DedupWithCount<Self> where Self::Item: PartialEq {
+        dedup::dedup_with_count(self)
+    }
+
+    /// As `dedup::dedup_by_key`.
+    fn dedup_by_key<F,K>(self, key: F) -> DedupByKey<Self,F,K>
+        where F: FnMut(&Self::Item) -> K, K: PartialEq {
+        dedup::dedup_by_key(self, key)
+    }
+
+    /// As `dedup::run_ranges`.
+    fn run_ranges<F>(self, same: F) -> RunRanges<Self,F>
+        where F: FnMut(&Self::Item, &Self::Item) -> bool {
+        dedup::run_ranges(self, same)
+    }
+
+    /// As `dedup::merge_adjacent`.
+    fn merge_adjacent<F>(self, f: F) -> MergeAdjacent<Self,F>
+        where F: FnMut(&Self::Item, &Self::Item) -> Option<Self::Item> {
+        dedup::merge_adjacent(self, f)
+    }
+
+    /// As `dedup::dedup_keep_last`.
+    fn dedup_keep_last<F>(self, same: F) -> DedupKeepLast<Self,F>
+        where F: FnMut(&Self::Item, &Self::Item) -> bool {
+        dedup::dedup_keep_last(self, same)
+    }
+
+    /// As `empty::empty_if`.
+    fn empty_if(self, cond: bool) -> EmptyIf<Self> {
+        empty::empty_if(self, cond)
+    }
+
+    /// As `result::map_while_ok`.
+    fn map_while_ok<F,T,E>(self, f: F) -> MapWhileOk<Self,F,E>
+        where F: FnMut(Self::Item) -> Result<T,E> {
+        result::map_while_ok(self, f)
+    }
+
+    /// As `result::map_ok`.
+    fn map_ok<F,T,U,E>(self, f: F) -> MapOk<Self,F>
+        where Self: Iterator<Item=Result<T,E>>, F: FnMut(T) -> U {
+        result::map_ok(self, f)
+    }
+
+    /// As `result::map_err`.
+    fn map_err<F,T,E,G>(self, f: F) -> MapErr<Self,F>
+        where Self: Iterator<Item=Result<T,E>>, F: FnMut(E) -> G {
+        result::map_err(self, f)
+    }
+
+    /// As `scan::try_scan`.
+    fn try_scan<S,F,T,E>(self, init: S, f: F) -> TryScan<Self,S,F>
+        where F: FnMut(&mut S, Self::Item) -> Result<Option<T>,E> {
+        scan::try_scan(self, init, f)
+    }
+
+    /// As `scan::scanl`.
+    fn scanl<B,F>(self, init: B, f: F) -> Scanl<Self,B,F>
+        where B: Clone, F: FnMut(&B, Self::Item) -> B {
+        scan::scanl(self, init, f)
+    }
+
+    /// As `scan::running_min`.
+    fn running_min(self) -> RunningMin<Self> where Self::Item: Ord + Clone {
+        scan::running_min(self)
+    }
+
+    /// As `scan::running_max`.
+    fn running_max(self) -> RunningMax<Self> where Self::Item: Ord + Clone {
+        scan::running_max(self)
+    }
+
+    /// As `scan::scan_indexed`.
+    fn scan_indexed<S,F,R>(self, init: S, f: F) -> ScanIndexed<Self,S,F>
+        where F: FnMut(&mut S, usize, Self::Item) -> Option<R> {
+        scan::scan_indexed(self, init, f)
+    }
+
+    /// As `scan::cumulative_counts`. Requires the `std` feature.
+    #[cfg(feature = "std")]
+    fn cumulative_counts(self) -> CumulativeCounts<Self> where Self::Item: Eq + Hash + Clone {
+        scan::cumulative_counts(self)
+    }
+
+    /// As `scan::repeat_last`.
+    fn repeat_last(self) -> RepeatLast<Self> where Self::Item: Clone {
+        scan::repeat_last(self)
+    }
+
+    /// As `scan::fill_missing`.
+    fn fill_missing<G,T>(self, step: usize, gen: G) -> FillMissing<Self,G>
+        where Self: Iterator<Item=(usize,T)>, G: FnMut(usize) -> T {
+        scan::fill_missing(self, step, gen)
+    }
+
+    /// As `select::step_by_indexed`.
+    fn step_by_indexed(self, step: usize) -> StepByIndexed<Self> {
+        select::step_by_indexed(self, step)
+    }
+
+    /// As `select::every_nth_matching`.
+    fn every_nth_matching<P>(self, n: usize, pred: P) -> EveryNthMatching<Self,P>
+        where P: FnMut(&Self::Item) -> bool {
+        select::every_nth_matching(self, n, pred)
+    }
+
+    /// As `select::flatten_options`.
+    fn flatten_options<T>(self) -> FlattenOptions<Self>
+        where Self: Iterator<Item=Option<T>> {
+        select::flatten_options(self)
+    }
+
+    /// As `select::positions`.
+    fn positions<P>(self, pred: P) -> Positions<Self,P>
+        where P: FnMut(&Self::Item) -> bool {
+        select::positions(self, pred)
+    }
+
+    /// As `windows::windows_map`.
+    fn windows_map<F,R>(self, size: usize, f: F) -> WindowsMap<Self,F>
+        where Self::Item: Clone, F: FnMut(&[Self::Item]) -> R {
+        windows::windows_map(self, size, f)
+    }
+
+    /// As `windows::sliding_reduce`.
+    fn sliding_reduce<A,F,G>(self, size: usize, add: F, sub: G, zero: A) -> SlidingReduce<Self,A,F,G>
+        where A: Clone, F: FnMut(A, &Self::Item) -> A, G: FnMut(A, &Self::Item) -> A {
+        windows::sliding_reduce(self, size, add, sub, zero)
+    }
+
+    /// As `windows::map_windows2`.
+    fn map_windows2<F,R>(self, f: F) -> MapWindows2<Self,F>
+        where F: FnMut(&Self::Item, &Self::Item) -> R {
+        windows::map_windows2(self, f)
+    }
+}
+
+impl<I> IterUtils for I where I: Iterator { }
+
+#[test]
+fn test1() {
+    let runs: Vec<(usize,i32)> = [1,1,2,3,3].iter().cloned().dedup_with_count().collect();
+    assert_eq!(runs, vec!{(2,1),(1,2),(2,3)});
+    let sums: Vec<i32> = (1..5).windows_map(2, |w| w[0] + w[1]).collect();
+    assert_eq!(sums, vec!{3,5,7});
+    let evens: Vec<usize> = [1,2,4,5].iter().positions(|n| *n % 2 == 0).collect();
+    assert_eq!(evens, vec!{1,2});
+}
+
+#[test]
+fn test2() {
+    let pairs: Vec<(i32,i32)> = (1..6).chunk_pairs().collect();
+    assert_eq!(pairs, vec!{(1,2),(3,4)});
+    let sums: Vec<i32> = (1..4).zip_with(10..13, |a, b| a + b).collect();
+    assert_eq!(sums, vec!{11,13,15});
+    let found: Vec<i32> = vec!{Some(1), None, Some(3)}.into_iter().flatten_options().collect();
+    assert_eq!(found, vec!{1,3});
+    let maxima: Vec<i32> = [2,1,3].iter().cloned().running_max().collect();
+    assert_eq!(maxima, vec!{2,2,3});
+}
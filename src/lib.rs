@@ -1,5 +1,31 @@
 //! Iterator utilities to reduce the burning and itching of doing weird things with iterators.
+//!
+//! With the default `std` feature disabled, the crate is `no_std` and
+//! only requires `alloc`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "std")]
+extern crate core;
+#[cfg_attr(not(feature = "std"), macro_use)]
+extern crate alloc;
+
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
 
 pub mod buffer;
+pub mod chunks;
+pub mod combine;
+pub mod dedup;
 pub mod empty;
 pub mod equivalence_class;
+pub mod group;
+pub mod iter_utils;
+pub mod reduce;
+pub mod result;
+pub mod scan;
+pub mod select;
+pub mod tokenize;
+pub mod windows;
@@ -0,0 +1,143 @@
+//! Functions reducing an iterator to a single result.
+
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+#[cfg(feature = "std")]
+use std::hash::Hash;
+
+/// Return the element of the argument iterator with the maximum
+/// value of `f`, along with that value. As with
+/// `Iterator::max_by_key`, the last of several maximal elements is
+/// returned. `f` is called once per element.
+pub fn max_with_key<I,K,F>(it: I, mut f: F) -> Option<(K,I::Item)>
+    where I: Iterator, K: Ord, F: FnMut(&I::Item) -> K {
+    it.map(|item| (f(&item), item))
+        .fold(None, |best, (k, item)| match best {
+            Some((ref bk, _)) if *bk > k => best,
+            _                            => Some((k, item)),
+        })
+}
+
+/// Return the element of the argument iterator with the minimum
+/// value of `f`, along with that value. As with
+/// `Iterator::min_by_key`, the first of several minimal elements is
+/// returned. `f` is called once per element.
+pub fn min_with_key<I,K,F>(it: I, mut f: F) -> Option<(K,I::Item)>
+    where I: Iterator, K: Ord, F: FnMut(&I::Item) -> K {
+    it.map(|item| (f(&item), item))
+        .fold(None, |best, (k, item)| match best {
+            Some((ref bk, _)) if *bk <= k => best,
+            _                             => Some((k, item)),
+        })
+}
+
+/// Fold `f` over each pair of adjacent elements of the argument
+/// iterator. With fewer than two elements, `init` is returned.
+pub fn pairwise_fold<I,B,F>(mut it: I, init: B, mut f: F) -> B
+    where I: Iterator, F: FnMut(B, &I::Item, &I::Item) -> B {
+    let mut prev = match it.next() {
+        Some(first) => first,
+        None        => return init,
+    };
+    let mut acc = init;
+    for item in it {
+        acc = f(acc, &prev, &item);
+        prev = item;
+    }
+    acc
+}
+
+/// Return the first element of the argument iterator equal to an
+/// earlier element, or None if all of the elements are distinct. No
+/// more of the iterator is read than necessary. Requires the `std`
+/// feature.
+#[cfg(feature = "std")]
+pub fn first_duplicate<I>(it: I) -> Option<I::Item> where I: Iterator, I::Item: Eq + Hash {
+    let mut seen = HashSet::new();
+    for item in it {
+        if seen.contains(&item) {
+            return Some(item);
+        }
+        seen.insert(item);
+    }
+    None
+}
+
+/// Split the pairs yielded by the argument iterator, appending the
+/// first fields to `lefts` and the second to `rights`. Existing
+/// contents are kept, so the Vecs can be reused across calls.
+pub fn unzip_into<I,A,B>(it: I, lefts: &mut Vec<A>, rights: &mut Vec<B>)
+    where I: Iterator<Item=(A,B)> {
+    let (lo, _) = it.size_hint();
+    lefts.reserve(lo);
+    rights.reserve(lo);
+    for (a, b) in it {
+        lefts.push(a);
+        rights.push(b);
+    }
+}
+
+/// Return the first element of the argument iterator along with
+/// the iterator itself, advanced past it, as `slice::split_first`.
+pub fn split_first<I>(mut it: I) -> Option<(I::Item,I)> where I: Iterator {
+    let first = it.next()?;
+    Some((first, it))
+}
+
+/// Return the last element of the argument iterator along with a
+/// Vec of the elements before it, as `slice::split_last`. The whole
+/// iterator is read and buffered.
+pub fn split_last<I>(it: I) -> Option<(I::Item,Vec<I::Item>)> where I: Iterator {
+    let mut init: Vec<I::Item> = it.collect();
+    let last = init.pop()?;
+    Some((last, init))
+}
+
+#[test]
+fn test1() {
+    let words = ["bb", "a", "ccc", "ddd", "e"];
+    assert_eq!(max_with_key(words.iter(), |w| w.len()), Some((3, &"ddd")));
+    assert_eq!(min_with_key(words.iter(), |w| w.len()), Some((1, &"a")));
+    assert_eq!(max_with_key(Vec::<&str>::new().into_iter(), |w| w.len()), None);
+}
+
+#[test]
+fn test2() {
+    let variation = |ns: &[i32]| pairwise_fold(ns.iter(), 0, |acc, l, r| acc + (*r - *l).abs());
+    assert_eq!(variation(&[1,4,2]), 5);
+    assert_eq!(variation(&[1]), 0);
+    assert_eq!(variation(&[]), 0);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test3() {
+    assert_eq!(first_duplicate(vec!{1,2,3,2,4}.into_iter()), Some(2));
+    assert_eq!(first_duplicate(vec!{1,2,3,4}.into_iter()), None);
+    let mut it = vec!{1,1,2}.into_iter();
+    assert_eq!(first_duplicate(it.by_ref()), Some(1));
+    assert_eq!(it.next(), Some(2));
+}
+
+#[test]
+fn test4() {
+    let mut lefts = Vec::new();
+    let mut rights = Vec::new();
+    unzip_into(vec!{(1,'a'),(2,'b')}.into_iter(), &mut lefts, &mut rights);
+    unzip_into(vec!{(3,'c')}.into_iter(), &mut lefts, &mut rights);
+    assert_eq!(lefts, vec!{1,2,3});
+    assert_eq!(rights, vec!{'a','b','c'});
+}
+
+#[test]
+fn test5() {
+    let (first, rest) = split_first(1..4).unwrap();
+    assert_eq!(first, 1);
+    assert_eq!(rest.collect::<Vec<_>>(), vec!{2,3});
+    assert!(split_first(0..0).is_none());
+    assert_eq!(split_last(1..4), Some((3, vec!{1,2})));
+    assert_eq!(split_last(1..2), Some((1, vec!{})));
+    assert_eq!(split_last(0..0), None);
+}
@@ -0,0 +1,115 @@
+//! Iterators over fallible computations.
+
+#[cfg(test)]
+use alloc::vec::Vec;
+
+pub struct MapWhileOk<I,F,E> {
+    iterator: I,
+    f:        F,
+    error:    Option<E>,
+}
+
+impl<I,F,E> MapWhileOk<I,F,E> {
+
+    /// The error that ended the iteration, if any.
+    pub fn into_error(self) -> Option<E> { self.error }
+}
+
+impl<I,F,T,E> Iterator for MapWhileOk<I,F,E> where I: Iterator, F: FnMut(I::Item) -> Result<T,E> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.error.is_some() {
+            return None;
+        }
+        match (self.f)(self.iterator.next()?) {
+            Ok(t)  => Some(t),
+            Err(e) => { self.error = Some(e); None }
+        }
+    }
+}
+
+/// Yield `t` for each element for which `f` returns `Ok(t)`, stopping
+/// at the first `Err`. The error is kept, and is available from
+/// `into_error`.
+pub fn map_while_ok<I,F,T,E>(it: I, f: F) -> MapWhileOk<I,F,E>
+    where I: Iterator, F: FnMut(I::Item) -> Result<T,E> {
+    MapWhileOk {
+        iterator: it,
+        f,
+        error:    None,
+    }
+}
+
+pub struct MapOk<I,F> {
+    iterator: I,
+    f:        F,
+}
+
+impl<I,F,T,U,E> Iterator for MapOk<I,F> where I: Iterator<Item=Result<T,E>>, F: FnMut(T) -> U {
+    type Item = Result<U,E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iterator.next().map(|res| res.map(&mut self.f))
+    }
+}
+
+/// Apply `f` to the contents of each `Ok` element of the argument
+/// iterator, leaving the `Err`s unchanged.
+///
+/// ```
+/// use iterator_utilities::result::map_ok;
+///
+/// let rs: Vec<Result<i32,String>> = vec!{Ok(1), Err("bad".to_string()), Ok(3)};
+/// let doubled: Vec<Result<i32,String>> = map_ok(rs.into_iter(), |n| n * 2).collect();
+/// assert_eq!(doubled, vec!{Ok(2), Err("bad".to_string()), Ok(6)});
+/// ```
+pub fn map_ok<I,F,T,U,E>(it: I, f: F) -> MapOk<I,F>
+    where I: Iterator<Item=Result<T,E>>, F: FnMut(T) -> U {
+    MapOk {
+        iterator: it,
+        f,
+    }
+}
+
+pub struct MapErr<I,F> {
+    iterator: I,
+    f:        F,
+}
+
+impl<I,F,T,E,G> Iterator for MapErr<I,F> where I: Iterator<Item=Result<T,E>>, F: FnMut(E) -> G {
+    type Item = Result<T,G>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iterator.next().map(|res| res.map_err(&mut self.f))
+    }
+}
+
+/// Apply `f` to the contents of each `Err` element of the argument
+/// iterator, leaving the `Ok`s unchanged.
+///
+/// ```
+/// use iterator_utilities::result::map_err;
+///
+/// let rs: Vec<Result<i32,&str>> = vec!{Ok(1), Err("bad")};
+/// let lengths: Vec<Result<i32,usize>> = map_err(rs.into_iter(), |e| e.len()).collect();
+/// assert_eq!(lengths, vec!{Ok(1), Err(3)});
+/// ```
+pub fn map_err<I,F,T,E,G>(it: I, f: F) -> MapErr<I,F>
+    where I: Iterator<Item=Result<T,E>>, F: FnMut(E) -> G {
+    MapErr {
+        iterator: it,
+        f,
+    }
+}
+
+#[test]
+fn test1() {
+    let mut ns = map_while_ok(vec!{"1","2","x","3"}.into_iter(), |s| s.parse::<i32>());
+    assert_eq!(ns.by_ref().collect::<Vec<_>>(), vec!{1,2});
+    assert_eq!(ns.next(), None);
+    assert!(ns.into_error().is_some());
+
+    let ns = map_while_ok(vec!{"1","2"}.into_iter(), |s| s.parse::<i32>());
+    assert!(ns.into_error().is_none());
+}
@@ -0,0 +1,352 @@
+//! Iterators carrying state from element to element.
+
+#[cfg(test)]
+use alloc::vec::Vec;
+use core::iter::Fuse;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::hash::Hash;
+
+pub struct TryScan<I,S,F> {
+    iterator: I,
+    state:    S,
+    f:        F,
+    done:     bool,
+}
+
+impl<I,S,F> TryScan<I,S,F> {
+
+    /// The current state. After an error, this is the state as left
+    /// by the failing call.
+    pub fn state(&self) -> &S { &self.state }
+}
+
+impl<I,S,F,T,E> Iterator for TryScan<I,S,F>
+    where I: Iterator, F: FnMut(&mut S, I::Item) -> Result<Option<T>,E> {
+    type Item = Result<T,E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let item = self.iterator.next()?;
+        match (self.f)(&mut self.state, item) {
+            Ok(Some(t)) => Some(Ok(t)),
+            Ok(None)    => { self.done = true; None }
+            Err(e)      => { self.done = true; Some(Err(e)) }
+        }
+    }
+}
+
+/// Like `Iterator::scan`, but `f` may fail. Each `Ok(Some(t))` is
+/// yielded as `Ok(t)`; `Ok(None)` ends the iteration, and an `Err` is
+/// yielded and then ends the iteration.
+pub fn try_scan<I,S,F,T,E>(it: I, init: S, f: F) -> TryScan<I,S,F>
+    where I: Iterator, F: FnMut(&mut S, I::Item) -> Result<Option<T>,E> {
+    TryScan {
+        iterator: it,
+        state:    init,
+        f,
+        done:     false,
+    }
+}
+
+pub struct Scanl<I,B,F> {
+    iterator: I,
+    acc:      B,
+    f:        F,
+    started:  bool,
+}
+
+impl<I,B,F> Iterator for Scanl<I,B,F> where I: Iterator, B: Clone, F: FnMut(&B, I::Item) -> B {
+    type Item = B;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+        } else {
+            let item = self.iterator.next()?;
+            self.acc = (self.f)(&self.acc, item);
+        }
+        Some(self.acc.clone())
+    }
+}
+
+/// Yield `init`, then each successive accumulator value computed by
+/// `f` from the previous value and the next element of the argument
+/// iterator.
+///
+/// ```
+/// use iterator_utilities::scan::scanl;
+///
+/// let sums: Vec<i32> = scanl(vec!{1,2,3}.into_iter(), 0, |acc, n| acc + n).collect();
+/// assert_eq!(sums, vec!{0,1,3,6});
+/// ```
+pub fn scanl<I,B,F>(it: I, init: B, f: F) -> Scanl<I,B,F>
+    where I: Iterator, B: Clone, F: FnMut(&B, I::Item) -> B {
+    Scanl {
+        iterator: it,
+        acc:      init,
+        f,
+        started:  false,
+    }
+}
+
+pub struct RunningMin<I> where I: Iterator {
+    iterator: I,
+    current:  Option<I::Item>,
+}
+
+impl<I> Iterator for RunningMin<I> where I: Iterator, I::Item: Ord + Clone {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iterator.next()?;
+        let min = match self.current.take() {
+            Some(current) if current <= item => current,
+            _                                => item,
+        };
+        self.current = Some(min.clone());
+        Some(min)
+    }
+}
+
+/// Yield the minimum of the elements of the argument iterator seen
+/// so far, at each element.
+///
+/// ```
+/// use iterator_utilities::scan::running_min;
+///
+/// let mins: Vec<i32> = running_min(vec!{3,1,4,1,5}.into_iter()).collect();
+/// assert_eq!(mins, vec!{3,1,1,1,1});
+/// ```
+pub fn running_min<I>(it: I) -> RunningMin<I> where I: Iterator, I::Item: Ord + Clone {
+    RunningMin {
+        iterator: it,
+        current:  None,
+    }
+}
+
+pub struct RunningMax<I> where I: Iterator {
+    iterator: I,
+    current:  Option<I::Item>,
+}
+
+impl<I> Iterator for RunningMax<I> where I: Iterator, I::Item: Ord + Clone {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iterator.next()?;
+        let max = match self.current.take() {
+            Some(current) if current >= item => current,
+            _                                => item,
+        };
+        self.current = Some(max.clone());
+        Some(max)
+    }
+}
+
+/// Yield the maximum of the elements of the argument iterator seen
+/// so far, at each element.
+///
+/// ```
+/// use iterator_utilities::scan::running_max;
+///
+/// let maxes: Vec<i32> = running_max(vec!{3,1,4,1,5}.into_iter()).collect();
+/// assert_eq!(maxes, vec!{3,3,4,4,5});
+/// ```
+pub fn running_max<I>(it: I) -> RunningMax<I> where I: Iterator, I::Item: Ord + Clone {
+    RunningMax {
+        iterator: it,
+        current:  None,
+    }
+}
+
+pub struct ScanIndexed<I,S,F> {
+    iterator: I,
+    state:    S,
+    f:        F,
+    index:    usize,
+}
+
+impl<I,S,F,R> Iterator for ScanIndexed<I,S,F> where I: Iterator, F: FnMut(&mut S, usize, I::Item) -> Option<R> {
+    type Item = R;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iterator.next()?;
+        let index = self.index;
+        self.index += 1;
+        (self.f)(&mut self.state, index, item)
+    }
+}
+
+/// Like `Iterator::scan`, but `f` also receives the index of each
+/// element of the argument iterator.
+pub fn scan_indexed<I,S,F,R>(it: I, init: S, f: F) -> ScanIndexed<I,S,F>
+    where I: Iterator, F: FnMut(&mut S, usize, I::Item) -> Option<R> {
+    ScanIndexed {
+        iterator: it,
+        state:    init,
+        f,
+        index:    0,
+    }
+}
+
+#[cfg(feature = "std")]
+pub struct CumulativeCounts<I> where I: Iterator {
+    iterator: I,
+    counts:   HashMap<I::Item,usize>,
+}
+
+#[cfg(feature = "std")]
+impl<I> Iterator for CumulativeCounts<I> where I: Iterator, I::Item: Eq + Hash + Clone {
+    type Item = (I::Item,usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iterator.next()?;
+        let count = self.counts.entry(item.clone()).or_insert(0);
+        *count += 1;
+        Some((item, *count))
+    }
+}
+
+/// Yield each element of the argument iterator with the number of
+/// times it has occurred so far, including this occurrence.
+/// Requires the `std` feature.
+///
+/// ```
+/// use iterator_utilities::scan::cumulative_counts;
+///
+/// let counts: Vec<(char,usize)> = cumulative_counts("abaa".chars()).collect();
+/// assert_eq!(counts, vec!{('a',1), ('b',1), ('a',2), ('a',3)});
+/// ```
+#[cfg(feature = "std")]
+pub fn cumulative_counts<I>(it: I) -> CumulativeCounts<I> where I: Iterator, I::Item: Eq + Hash + Clone {
+    CumulativeCounts {
+        iterator: it,
+        counts:   HashMap::new(),
+    }
+}
+
+pub struct RepeatLast<I> where I: Iterator {
+    iterator: Fuse<I>,
+    last:     Option<I::Item>,
+}
+
+impl<I> Iterator for RepeatLast<I> where I: Iterator, I::Item: Clone {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.iterator.next() {
+            Some(item) => { self.last = Some(item.clone()); Some(item) }
+            None       => self.last.clone(),
+        }
+    }
+}
+
+/// Yield the elements of the argument iterator, then repeat the last
+/// element forever. The result is infinite unless the argument
+/// iterator is empty, in which case so is the result.
+pub fn repeat_last<I>(it: I) -> RepeatLast<I> where I: Iterator, I::Item: Clone {
+    RepeatLast {
+        iterator: it.fuse(),
+        last:     None,
+    }
+}
+
+pub struct FillMissing<I,G> where I: Iterator {
+    iterator: I,
+    step:     usize,
+    gen:      G,
+    expected: Option<usize>,
+    pending:  Option<I::Item>,
+}
+
+impl<I,G,T> Iterator for FillMissing<I,G> where I: Iterator<Item=(usize,T)>, G: FnMut(usize) -> T {
+    type Item = (usize,T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.pending.take().or_else(|| self.iterator.next())?;
+        match self.expected {
+            Some(expected) if expected < item.0 => {
+                self.pending = Some(item);
+                self.expected = expected.checked_add(self.step);
+                Some((expected, (self.gen)(expected)))
+            }
+            _ => {
+                self.expected = item.0.checked_add(self.step);
+                Some(item)
+            }
+        }
+    }
+}
+
+/// Yield the indexed elements of the argument iterator, which must
+/// be in increasing order of index, inserting `(i, gen(i))` for each
+/// index `i` missing from the sequence stepping by `step` from the
+/// first index. Nothing is inserted past `usize::MAX`. Panics if
+/// `step` is 0.
+pub fn fill_missing<I,G,T>(it: I, step: usize, gen: G) -> FillMissing<I,G>
+    where I: Iterator<Item=(usize,T)>, G: FnMut(usize) -> T {
+    assert!(step != 0, "step must be non-zero");
+    FillMissing {
+        iterator: it,
+        step,
+        gen,
+        expected: None,
+        pending:  None,
+    }
+}
+
+#[test]
+fn test1() {
+    let mut sums = try_scan("12x4".chars(), 0, |sum, c| {
+        let d = c.to_digit(10).ok_or(c)?;
+        *sum += d;
+        Ok(Some(*sum))
+    });
+    assert_eq!(sums.next(), Some(Ok(1)));
+    assert_eq!(sums.next(), Some(Ok(3)));
+    assert_eq!(sums.next(), Some(Err('x')));
+    assert_eq!(sums.next(), None);
+    assert_eq!(*sums.state(), 3);
+}
+
+#[test]
+fn test2() {
+    let sums: Vec<i32> = scan_indexed(10..20, 0, |sum, i, n| {
+        if i == 3 {
+            None
+        } else {
+            *sum += n;
+            Some(*sum)
+        }
+    }).collect();
+    assert_eq!(sums, vec!{10,21,33});
+}
+
+#[test]
+fn test3() {
+    let padded: Vec<(i32,char)> = (0..5).zip(repeat_last("ab".chars())).collect();
+    assert_eq!(padded, vec!{(0,'a'), (1,'b'), (2,'b'), (3,'b'), (4,'b')});
+    assert_eq!(repeat_last(0..0).next(), None);
+}
+
+#[test]
+fn test4() {
+    let readings = vec!{(0, 1.0), (1, 1.5), (4, 3.0), (5, 2.5)};
+    let filled: Vec<(usize,f64)> = fill_missing(readings.into_iter(), 1, |_| 0.0).collect();
+    assert_eq!(filled, vec!{(0, 1.0), (1, 1.5), (2, 0.0), (3, 0.0), (4, 3.0), (5, 2.5)});
+    let filled: Vec<(usize,usize)> = fill_missing(vec!{(10, 10), (40, 40)}.into_iter(), 10, |i| i).collect();
+    assert_eq!(filled, vec!{(10, 10), (20, 20), (30, 30), (40, 40)});
+}
+
+#[test]
+fn test5() {
+    let filled: Vec<(usize,i32)> = fill_missing(vec!{(usize::MAX, 0)}.into_iter(), 1, |_| 1).collect();
+    assert_eq!(filled, vec!{(usize::MAX, 0)});
+    let half = usize::MAX / 2 + 1;
+    let filled: Vec<(usize,i32)> = fill_missing(vec!{(0, 0), (usize::MAX, 0)}.into_iter(), half, |_| 1).collect();
+    assert_eq!(filled, vec!{(0, 0), (half, 1), (usize::MAX, 0)});
+}
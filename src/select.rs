@@ -0,0 +1,174 @@
+//! Iterators selecting some of the elements of an iterator.
+
+#[cfg(test)]
+use alloc::vec::Vec;
+
+pub struct StepByIndexed<I> {
+    iterator: I,
+    step:     usize,
+    index:    usize,
+}
+
+impl<I> Iterator for StepByIndexed<I> where I: Iterator {
+    type Item = (usize,I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = if self.index == 0 {
+            self.iterator.next()
+        } else {
+            self.iterator.nth(self.step - 1)
+        }?;
+        let res = (self.index, item);
+        self.index += self.step;
+        Some(res)
+    }
+}
+
+/// Like `Iterator::step_by`, yield every `step`th element of the
+/// argument iterator, starting with the first, along with its index
+/// in the iterator. Panics if `step` is 0.
+pub fn step_by_indexed<I>(it: I, step: usize) -> StepByIndexed<I> where I: Iterator {
+    assert!(step != 0, "step must be non-zero");
+    StepByIndexed {
+        iterator: it,
+        step,
+        index:    0,
+    }
+}
+
+pub struct EveryNthMatching<I,P> {
+    iterator: I,
+    n:        usize,
+    pred:     P,
+    skip:     usize,
+}
+
+impl<I,P> Iterator for EveryNthMatching<I,P> where I: Iterator, P: FnMut(&I::Item) -> bool {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let item = self.iterator.next()?;
+            if (self.pred)(&item) {
+                if self.skip == 0 {
+                    self.skip = self.n - 1;
+                    return Some(item);
+                }
+                self.skip -= 1;
+            }
+        }
+    }
+}
+
+/// Yield every `n`th element of the argument iterator among those
+/// satisfying `pred`. The elements are filtered first, then stepped
+/// as by `Iterator::step_by`: the first matching element is yielded,
+/// then the `n+1`th, and so on. Panics if `n` is 0.
+pub fn every_nth_matching<I,P>(it: I, n: usize, pred: P) -> EveryNthMatching<I,P>
+    where I: Iterator, P: FnMut(&I::Item) -> bool {
+    assert!(n != 0, "n must be non-zero");
+    EveryNthMatching {
+        iterator: it,
+        n,
+        pred,
+        skip:     0,
+    }
+}
+
+pub struct FlattenOptions<I> {
+    iterator: I,
+}
+
+impl<I,T> Iterator for FlattenOptions<I> where I: Iterator<Item=Option<T>> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iterator.by_ref().find_map(|opt| opt)
+    }
+}
+
+/// Yield the contents of each `Some` element of the argument
+/// iterator, skipping the `None`s.
+///
+/// ```
+/// use iterator_utilities::select::flatten_options;
+///
+/// let ns: Vec<i32> = flatten_options(vec!{Some(1), None, Some(3)}.into_iter()).collect();
+/// assert_eq!(ns, vec!{1,3});
+/// ```
+pub fn flatten_options<I,T>(it: I) -> FlattenOptions<I> where I: Iterator<Item=Option<T>> {
+    FlattenOptions { iterator: it }
+}
+
+pub struct Positions<I,P> {
+    iterator: I,
+    pred:     P,
+    index:    usize,
+}
+
+impl<I,P> Iterator for Positions<I,P> where I: Iterator, P: FnMut(&I::Item) -> bool {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let item = self.iterator.next()?;
+            let index = self.index;
+            self.index += 1;
+            if (self.pred)(&item) {
+                return Some(index);
+            }
+        }
+    }
+}
+
+impl<I,P> DoubleEndedIterator for Positions<I,P>
+    where I: DoubleEndedIterator + ExactSizeIterator, P: FnMut(&I::Item) -> bool {
+
+    /// Yield matching indices from the end, like `Iterator::rposition`.
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            let index = self.index + self.iterator.len();
+            let item = self.iterator.next_back()?;
+            if (self.pred)(&item) {
+                return Some(index - 1);
+            }
+        }
+    }
+}
+
+/// Yield the index of every element of the argument iterator
+/// satisfying `pred`. If the iterator is double-ended and of known
+/// length, so is the result.
+pub fn positions<I,P>(it: I, pred: P) -> Positions<I,P> where I: Iterator, P: FnMut(&I::Item) -> bool {
+    Positions {
+        iterator: it,
+        pred,
+        index:    0,
+    }
+}
+
+#[test]
+fn test1() {
+    let kept: Vec<(usize,char)> = step_by_indexed("abcdefghij".chars(), 3).collect();
+    assert_eq!(kept, vec!{(0,'a'), (3,'d'), (6,'g'), (9,'j')});
+    let kept: Vec<(usize,i32)> = step_by_indexed(0..10, 3).collect();
+    assert_eq!(kept, vec!{(0,0), (3,3), (6,6), (9,9)});
+}
+
+#[test]
+fn test2() {
+    let sampled: Vec<i32> = every_nth_matching(0..20, 2, |n| n % 2 == 0).collect();
+    assert_eq!(sampled, vec!{0,4,8,12,16});
+}
+
+#[test]
+fn test3() {
+    let ns = [1,2,3,4,5,6];
+    let evens: Vec<usize> = positions(ns.iter(), |&&n| n % 2 == 0).collect();
+    assert_eq!(evens, vec!{1,3,5});
+    let mut evens = positions(ns.iter(), |&&n| n % 2 == 0);
+    assert_eq!(evens.next_back(), Some(5));
+    assert_eq!(evens.next(), Some(1));
+    assert_eq!(evens.next_back(), Some(3));
+    assert_eq!(evens.next(), None);
+}
@@ -0,0 +1,137 @@
+//! A tokenizer skeleton built on the iterator buffer.
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use buffer::IteratorBuffer;
+
+/// A matcher returns the length of the token at the front of a slice.
+type Matcher<'r,T> = Box<dyn Fn(&[T]) -> Option<usize> + 'r>;
+
+/// Split a stream into tokens using a list of rules. Each rule pairs
+/// a token kind with a matcher, which is given the buffered front of
+/// the stream and returns the length of the token it recognizes
+/// there, if any. Matchers may borrow data, such as a keyword table,
+/// living for `'r`.
+pub struct Tokenizer<'r,I,K> where I: Iterator {
+    buffer: IteratorBuffer<I>,
+    rules:  Vec<(K,Matcher<'r,I::Item>)>,
+}
+
+impl<'r,I,K> Tokenizer<'r,I,K> where I: Iterator, I::Item: Clone {
+
+    /// Create a tokenizer for Iterator it, with no rules. Matchers
+    /// first see the next `lookahead` elements, or fewer at the end
+    /// of the stream. A match covering all of them is retried with
+    /// more of the stream, so tokens are not limited to `lookahead`
+    /// elements. Panics if `lookahead` is 0.
+    pub fn new(it: I, lookahead: usize) -> Tokenizer<'r,I,K> {
+        assert!(lookahead != 0, "lookahead must be non-zero");
+        Tokenizer {
+            buffer: IteratorBuffer::new(it, lookahead - 1),
+            rules:  Vec::new(),
+        }
+    }
+
+    /// Add a rule recognizing tokens of the given kind. A match of
+    /// length 0 is treated as no match.
+    pub fn add_rule<M>(&mut self, kind: K, matcher: M)
+        where M: Fn(&[I::Item]) -> Option<usize> + 'r {
+        self.rules.push((kind, Box::new(matcher)));
+    }
+
+    /// Return the index of the rule with the longest match at the
+    /// front of the buffer, and the length of the match.
+    fn longest_rule(&self) -> Option<(usize,usize)> {
+        let front = self.buffer.buffer();
+        let mut best: Option<(usize,usize)> = None;
+        for (i, (_, matcher)) in self.rules.iter().enumerate() {
+            if let Some(len) = matcher(front) {
+                let len = len.min(front.len());
+                if len > 0 && best.is_none_or(|(_, best_len)| best_len < len) {
+                    best = Some((i, len));
+                }
+            }
+        }
+        best
+    }
+}
+
+impl<'r,I,K> Iterator for Tokenizer<'r,I,K> where I: Iterator, I::Item: Clone, K: Clone {
+    type Item = Result<(K,Vec<I::Item>),I::Item>;
+
+    /// Yield the longest token matched by any rule at the front of
+    /// the stream, preferring the earliest rule among equally long
+    /// matches. While the longest match covers the whole buffer and
+    /// the stream continues, the buffer is doubled and the rules
+    /// applied again. An element at which no rule matches is yielded
+    /// alone, as an error.
+    fn next(&mut self) -> Option<Self::Item> {
+        self.buffer.peek()?;
+        let mut best = self.longest_rule();
+        while let Some((_, len)) = best {
+            if len < self.buffer.len() || !self.buffer.has_more() {
+                break;
+            }
+            self.buffer.get_or_fill(2 * len - 1);
+            best = self.longest_rule();
+        }
+        match best {
+            Some((i, len)) => {
+                let token = self.buffer.take_chunk(len).unwrap_or_default();
+                Some(Ok((self.rules[i].0.clone(), token)))
+            }
+            None => self.buffer.pop().map(Err),
+        }
+    }
+}
+
+#[test]
+fn test1() {
+    #[derive(Clone, Debug, PartialEq)]
+    enum Kind { Number, Operator }
+
+    let mut tokens = Tokenizer::new("12+3*45?".chars(), 4);
+    tokens.add_rule(Kind::Number, |s: &[char]| {
+        let n = s.iter().take_while(|c| c.is_ascii_digit()).count();
+        if n > 0 { Some(n) } else { None }
+    });
+    tokens.add_rule(Kind::Operator, |s: &[char]| {
+        if "+-*/".contains(s[0]) { Some(1) } else { None }
+    });
+    let tokens: Vec<_> = tokens.collect();
+    assert_eq!(tokens, vec!{
+        Ok((Kind::Number, vec!{'1','2'})),
+        Ok((Kind::Operator, vec!{'+'})),
+        Ok((Kind::Number, vec!{'3'})),
+        Ok((Kind::Operator, vec!{'*'})),
+        Ok((Kind::Number, vec!{'4','5'})),
+        Err('?'),
+    });
+}
+
+#[test]
+fn test2() {
+    let mut tokens = Tokenizer::new("1234567+89".chars(), 2);
+    tokens.add_rule("number", |s: &[char]| {
+        let n = s.iter().take_while(|c| c.is_ascii_digit()).count();
+        if n > 0 { Some(n) } else { None }
+    });
+    let tokens: Vec<_> = tokens.collect();
+    assert_eq!(tokens, vec!{
+        Ok(("number", "1234567".chars().collect())),
+        Err('+'),
+        Ok(("number", vec!{'8','9'})),
+    });
+}
+
+#[test]
+fn test3() {
+    let keywords = ["let", "in"];
+    let mut tokens = Tokenizer::new("let x in".chars(), 3);
+    tokens.add_rule("keyword", |s: &[char]| {
+        keywords.iter().find(|k| s.iter().take(k.len()).cloned().eq(k.chars())).map(|k| k.len())
+    });
+    tokens.add_rule("space", |s: &[char]| if s[0] == ' ' { Some(1) } else { None });
+    let kinds: Vec<_> = tokens.map(|token| token.map(|(kind, _)| kind)).collect();
+    assert_eq!(kinds, vec!{Ok("keyword"), Ok("space"), Err('x'), Ok("space"), Ok("keyword")});
+}
@@ -0,0 +1,224 @@
+//! Iterators over sliding windows of the elements of an iterator.
+
+use alloc::collections::VecDeque;
+#[cfg(test)]
+use alloc::vec::Vec;
+use buffer::IteratorBuffer;
+
+pub struct WindowsMap<I,F> where I: Iterator {
+    buffer: IteratorBuffer<I>,
+    size:   usize,
+    f:      F,
+}
+
+impl<I,F,R> Iterator for WindowsMap<I,F> where I: Iterator, I::Item: Clone, F: FnMut(&[I::Item]) -> R {
+    type Item = R;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buffer.len() < self.size {
+            None
+        } else {
+            let res = (self.f)(&self.buffer.buffer()[..self.size]);
+            self.buffer.pop();
+            Some(res)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lo, hi) = self.buffer.size_hint();
+        let windows = |n: usize| n.saturating_sub(self.size - 1);
+        (windows(lo), hi.map(windows))
+    }
+}
+
+impl<I,F,R> ExactSizeIterator for WindowsMap<I,F>
+    where I: ExactSizeIterator, I::Item: Clone, F: FnMut(&[I::Item]) -> R { }
+
+/// Apply `f` to each window of `size` adjacent elements of the
+/// argument iterator, yielding the results. The windows are slices
+/// of a single internal buffer, so no Vec is allocated per window,
+/// but the buffer is shifted at each step, costing O(size). Panics
+/// if `size` is 0.
+///
+/// ```
+/// use iterator_utilities::windows::windows_map;
+///
+/// let sums: Vec<i32> = windows_map(vec!{1,2,3,4}.into_iter(), 2, |w| w.iter().sum()).collect();
+/// assert_eq!(sums, vec!{3,5,7});
+/// ```
+pub fn windows_map<I,F,R>(it: I, size: usize, f: F) -> WindowsMap<I,F>
+    where I: Iterator, I::Item: Clone, F: FnMut(&[I::Item]) -> R {
+        assert!(size != 0, "window size must be non-zero");
+        WindowsMap {
+            buffer: IteratorBuffer::new(it, size - 1),
+            size,
+            f,
+        }
+    }
+
+/// Return true if any window of `size` adjacent elements of the
+/// argument iterator satisfies `pred`, stopping at the first that
+/// does. Panics if `size` is 0.
+pub fn contains_window<I,P>(it: I, size: usize, pred: P) -> bool
+    where I: Iterator, I::Item: Clone, P: FnMut(&[I::Item]) -> bool {
+        windows_map(it, size, pred).any(|found| found)
+    }
+
+pub struct SlidingReduce<I,A,F,G> where I: Iterator {
+    iterator: I,
+    window:   VecDeque<I::Item>,
+    size:     usize,
+    acc:      Option<A>,
+    zero:     Option<A>,
+    add:      F,
+    sub:      G,
+}
+
+impl<I,A,F,G> Iterator for SlidingReduce<I,A,F,G>
+    where I: Iterator, A: Clone, F: FnMut(A, &I::Item) -> A, G: FnMut(A, &I::Item) -> A {
+    type Item = A;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let acc = match self.acc.take() {
+            None => {
+                let zero = self.zero.take()?;
+                while self.window.len() < self.size {
+                    self.window.push_back(self.iterator.next()?);
+                }
+                self.window.iter().fold(zero, &mut self.add)
+            }
+            Some(acc) => {
+                let item = self.iterator.next()?;
+                let acc = match self.window.pop_front() {
+                    Some(old) => (self.sub)(acc, &old),
+                    None      => acc,
+                };
+                let acc = (self.add)(acc, &item);
+                self.window.push_back(item);
+                acc
+            }
+        };
+        self.acc = Some(acc.clone());
+        Some(acc)
+    }
+}
+
+/// Yield an aggregate of each window of `size` adjacent elements of
+/// the argument iterator, maintained incrementally: `add` folds the
+/// element entering the window into the aggregate and `sub` removes
+/// the element leaving it. The window is kept in a VecDeque, so each
+/// step costs O(1) rather than O(size). The first window is folded
+/// from `zero`. `sub` must undo `add`, or the aggregates drift from
+/// the windows' contents. Panics if `size` is 0.
+pub fn sliding_reduce<I,A,F,G>(it: I, size: usize, add: F, sub: G, zero: A) -> SlidingReduce<I,A,F,G>
+    where I: Iterator, A: Clone, F: FnMut(A, &I::Item) -> A, G: FnMut(A, &I::Item) -> A {
+        assert!(size != 0, "window size must be non-zero");
+        SlidingReduce {
+            iterator: it,
+            window:   VecDeque::with_capacity(size),
+            size,
+            acc:      None,
+            zero:     Some(zero),
+            add,
+            sub,
+        }
+    }
+
+pub struct MapWindows2<I,F> where I: Iterator {
+    iterator: I,
+    prev:     Option<I::Item>,
+    f:        F,
+}
+
+impl<I,F,R> Iterator for MapWindows2<I,F> where I: Iterator, F: FnMut(&I::Item, &I::Item) -> R {
+    type Item = R;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.prev.is_none() {
+            self.prev = Some(self.iterator.next()?);
+        }
+        let cur = self.iterator.next()?;
+        let prev = self.prev.replace(cur)?;
+        Some((self.f)(&prev, self.prev.as_ref()?))
+    }
+}
+
+/// Apply `f` to each pair of adjacent elements of the argument
+/// iterator, yielding the results. Only the previous element is
+/// held, so, unlike `windows_map`, the elements need not be Clone.
+///
+/// ```
+/// use iterator_utilities::windows::map_windows2;
+///
+/// let ratios: Vec<f64> = map_windows2(vec!{1.0,2.0,3.0,6.0}.into_iter(), |a, b| b / a).collect();
+/// assert_eq!(ratios, vec!{2.0,1.5,2.0});
+/// ```
+pub fn map_windows2<I,F,R>(it: I, f: F) -> MapWindows2<I,F>
+    where I: Iterator, F: FnMut(&I::Item, &I::Item) -> R {
+        MapWindows2 {
+            iterator: it,
+            prev:     None,
+            f,
+        }
+    }
+
+#[test]
+fn test1() {
+    let sums: Vec<i32> = windows_map(vec!{1,2,3,4}.into_iter(), 3, |w| w.iter().sum()).collect();
+    assert_eq!(sums, vec!{6,9});
+    let mut short = windows_map(vec!{1,2}.into_iter(), 3, |w: &[i32]| w.len());
+    assert_eq!(short.next(), None);
+}
+
+#[test]
+fn test2() {
+    let increasing = |w: &[i32]| w[0] < w[1] && w[1] < w[2];
+    assert!(contains_window(vec!{5,1,2,1,3,4,6}.into_iter(), 3, increasing));
+    assert!(!contains_window(vec!{5,1,2,1,3,2,6}.into_iter(), 3, increasing));
+    assert!(!contains_window(vec!{1,2}.into_iter(), 3, increasing));
+}
+
+#[test]
+fn test3() {
+    let mut windows = windows_map(0..5, 2, |w| w[0] + w[1]);
+    assert_eq!(windows.len(), 4);
+    windows.next();
+    assert_eq!(windows.len(), 3);
+    assert_eq!(windows.by_ref().count(), 3);
+    assert_eq!(windows.len(), 0);
+    assert_eq!(windows_map(0..2, 3, |w| w[0]).len(), 0);
+    assert_eq!(windows_map((0..5).filter(|_| true), 2, |w| w[0]).size_hint(), (1, Some(4)));
+}
+
+#[test]
+fn test4() {
+    let sums: Vec<i32> = sliding_reduce(1..7, 3, |acc, x| acc + x, |acc, x| acc - x, 0).collect();
+    assert_eq!(sums, vec!{6,9,12,15});
+    let mut short = sliding_reduce(1..3, 3, |acc, x| acc + x, |acc, x| acc - x, 0);
+    assert_eq!(short.next(), None);
+    let sums: Vec<i32> = sliding_reduce(1..4, 3, |acc, x| acc + x, |acc, x| acc - x, 0).collect();
+    assert_eq!(sums, vec!{6});
+}
+
+#[test]
+fn test5() {
+    let diffs: Vec<i32> = map_windows2(vec!{1,4,9,16}.into_iter(), |a, b| b - a).collect();
+    assert_eq!(diffs, vec!{3,5,7});
+    assert_eq!(map_windows2(0..1, |a, b| a + b).next(), None);
+    assert_eq!(map_windows2(0..0, |a, b| a + b).next(), None);
+}
+
+#[test]
+fn test6() {
+    let sums: Vec<u32> = windows_map(0u32.., 2, |w| w[0] + w[1]).take(3).collect();
+    assert_eq!(sums, vec!{1,3,5});
+    assert_eq!(windows_map(0.., 3, |w| w[0]).size_hint(), (usize::MAX - 2, None));
+}
+
+#[test]
+fn test7() {
+    struct Reading(i32);
+    let readings = (1..6).map(Reading);
+    let sums: Vec<i32> = sliding_reduce(readings, 2, |acc, r| acc + r.0, |acc, r| acc - r.0, 0).collect();
+    assert_eq!(sums, vec!{3,5,7,9});
+}